@@ -0,0 +1,62 @@
+//! Build and query a `GridIndex` over 1M points, as `Markers` does for large scatters.
+//! Run with `cargo bench --bench grid_index`
+
+use gpui_plot::geometry::GridIndex;
+use std::hint::black_box;
+use std::time::Instant;
+
+const POINTS: u64 = 1_000_000;
+const QUERIES: usize = 1_000;
+
+/// A deterministic spread over [0, 1000) on both axes
+fn points() -> Vec<(f64, f64)> {
+    (0..POINTS)
+        .map(|i| {
+            let x = i.wrapping_mul(2_654_435_761) % POINTS;
+            let y = i.wrapping_mul(40_503) % POINTS;
+            (x as f64 / 1000.0, y as f64 / 1000.0)
+        })
+        .collect()
+}
+
+fn bench_queries(index: &GridIndex, name: &str, span: f64) {
+    let start = Instant::now();
+    let mut found = 0;
+    for q in 0..QUERIES {
+        let origin = (q as f64 * 7.919) % (1000.0 - span);
+        found += black_box(index.query((origin, origin + span), (origin, origin + span))).len();
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{name}: {:?} per query, {} candidates on average",
+        elapsed / QUERIES as u32,
+        found / QUERIES
+    );
+}
+
+fn main() {
+    let points = points();
+    let start = Instant::now();
+    let index = GridIndex::build(&points);
+    println!("build {POINTS} points: {:?}", start.elapsed());
+
+    bench_queries(&index, "deep zoom (0.1% of each axis)", 1.0);
+    bench_queries(&index, "zoom (10% of each axis)", 100.0);
+    bench_queries(&index, "full view", 999.0);
+
+    let start = Instant::now();
+    let mut found = 0;
+    for q in 0..QUERIES / 100 {
+        let origin = (q as f64 * 7.919) % 999.0;
+        found += black_box(&points)
+            .iter()
+            .filter(|p| (origin..=origin + 1.0).contains(&p.0))
+            .filter(|p| (origin..=origin + 1.0).contains(&p.1))
+            .count();
+    }
+    println!(
+        "linear scan baseline (deep zoom): {:?} per query, {} matches",
+        start.elapsed() / (QUERIES / 100) as u32,
+        found / (QUERIES / 100)
+    );
+}
@@ -86,7 +86,7 @@ impl Render for MainView {
 
                     chart
                         .draw_series(LineSeries::new(
-                            line.points.iter().map(|p| (p.x, p.y)),
+                            line.points().iter().map(|p| (p.x, p.y)),
                             &RED,
                         ))
                         .unwrap();
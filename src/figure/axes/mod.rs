@@ -1,18 +1,32 @@
 mod model;
 #[cfg(feature = "plotters")]
 mod plotters;
+mod selection;
 mod view;
 
 pub use model::*;
 #[cfg(feature = "plotters")]
 pub use plotters::*;
+pub use selection::*;
 use std::any::Any;
 pub use view::*;
 
-use crate::geometry::{AxesBounds, AxesBoundsPixels, AxisType, GeometryAxes, Point2};
-use gpui::{App, Bounds, MouseMoveEvent, Pixels, Point, Window};
+use crate::geometry::{
+    AxesBounds, AxesBoundsPixels, AxisType, FontConfig, GeometryAxes, Point2, Series,
+};
+use gpui::{App, Bounds, Edges, Hsla, MouseMoveEvent, Pixels, Point, SharedString, Window};
+
+/// Which renderer draws an axes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderBackend {
+    /// The elements of an `AxesModel`, drawn through `GeometryAxes`
+    Native,
+    /// A plotters chart, see `PlottersModel`, optionally with the native elements on top
+    Plotters,
+}
 
 pub trait Axes: Any {
+    fn backend(&self) -> RenderBackend;
     fn update(&mut self);
     fn new_render(&mut self);
     fn pan_begin(&mut self, position: Point<Pixels>);
@@ -20,13 +34,51 @@ pub trait Axes: Any {
     fn pan_end(&mut self);
     fn zoom_begin(&mut self, position: Point<Pixels>);
     fn zoom(&mut self, factor: f64);
+    /// Zoom each axis by its own factor, relative to where the gesture began
+    fn zoom_xy(&mut self, factor_x: f64, factor_y: f64);
     fn zoom_end(&mut self);
+    /// Zoom so that the pixel rect `bounds` fills the plotting area
+    fn zoom_to_pixel_bounds(&mut self, bounds: Bounds<Pixels>);
+    /// The cursor moved over the plot, or left it when `None`.
+    /// Returns whether that changed anything drawn, so a repaint is needed
+    fn hover(&mut self, position: Option<Point<Pixels>>) -> bool;
+    /// A brush is being dragged over the pixel x span `start..end`
+    fn brush(&mut self, start: Pixels, end: Pixels);
+    /// Append the point under the pixel `position` to the element at `element`.
+    /// Returns whether it was added
+    fn add_point_at(&mut self, element: usize, position: Point<Pixels>) -> bool;
+    /// Space needed around the plotting area for ticks and labels
+    fn margins(&self, window: &Window) -> Edges<Pixels>;
+    /// The margins set by `set_min_margins`
+    fn min_margins(&self) -> Edges<Pixels>;
+    /// Keep the margins at least this wide, e.g. to line up stacked plots.
+    /// Axes that lay out their own margins may ignore it
+    fn set_min_margins(&mut self, margins: Edges<Pixels>);
+    /// Apply a figure's typography to the text on these axes
+    fn set_font(&mut self, font: &FontConfig);
+    /// Call `f` with every series plotted on these axes
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series));
     fn render(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App);
 }
 
 pub struct AxesContext<'a, X: AxisType, Y: AxisType> {
     pub axes_bounds: AxesBounds<X, Y>,
     pub pixel_bounds: AxesBoundsPixels,
+    /// Geometry is clipped to this rect unless it opts out. `None` disables clipping
+    pub clip_rect: Option<Bounds<Pixels>>,
+    /// The plotting area, for geometry that must stay inside the axes
+    pub data_clip: Bounds<Pixels>,
+    /// The plotting area plus its margins, for geometry drawn at the axes edges
+    pub full_bounds: Bounds<Pixels>,
+    /// Lines are never stroked thinner than this
+    pub min_line_width: Pixels,
+    /// The union of what geometry reported painting, see `report_painted`
+    pub painted_bounds: Option<Bounds<Pixels>>,
+    /// Scales the alpha of the colors series paint, e.g. to dim all but the hovered one
+    pub opacity: f32,
+    /// Size and family of text drawn over the plotting area, see `AxesModel::label_font_size`
+    pub label_font_size: Pixels,
+    pub font_family: Option<SharedString>,
     pub cx: Option<(&'a mut Window, &'a mut App)>,
 }
 impl<'a, X: AxisType, Y: AxisType> AxesContext<'a, X, Y> {
@@ -34,6 +86,14 @@ impl<'a, X: AxisType, Y: AxisType> AxesContext<'a, X, Y> {
         Self {
             axes_bounds: model.axes_bounds,
             pixel_bounds: model.pixel_bounds,
+            clip_rect: Some(model.clip_rect()),
+            data_clip: model.data_clip(),
+            full_bounds: model.full_bounds(),
+            min_line_width: model.min_line_width,
+            painted_bounds: None,
+            opacity: 1.0,
+            label_font_size: model.label_font_size,
+            font_family: model.font_family.clone(),
             cx: Some((window, cx)),
         }
     }
@@ -41,12 +101,36 @@ impl<'a, X: AxisType, Y: AxisType> AxesContext<'a, X, Y> {
         Self {
             axes_bounds: model.axes_bounds,
             pixel_bounds: model.pixel_bounds,
+            clip_rect: Some(model.clip_rect()),
+            data_clip: model.data_clip(),
+            full_bounds: model.full_bounds(),
+            min_line_width: model.min_line_width,
+            painted_bounds: None,
+            opacity: 1.0,
+            label_font_size: model.label_font_size,
+            font_family: model.font_family.clone(),
             cx: None,
         }
     }
     pub fn transform_point(&self, point: Point2<X, Y>) -> Point<Pixels> {
         self.axes_bounds.transform_point(self.pixel_bounds, point)
     }
+    /// `color` with `opacity` applied, for series to paint with
+    pub fn fade(&self, color: Hsla) -> Hsla {
+        color.opacity(self.opacity)
+    }
+    /// Record that geometry painted over `bounds`
+    pub fn report_painted(&mut self, bounds: Bounds<Pixels>) {
+        self.painted_bounds = Some(match self.painted_bounds {
+            Some(painted) => painted.union(&bounds),
+            None => bounds,
+        });
+    }
+    /// Transform `points` in bulk into the caller's buffer
+    pub fn transform_points(&self, points: &[Point2<X, Y>], out: &mut Vec<Point<Pixels>>) {
+        self.axes_bounds
+            .transform_points(self.pixel_bounds, points, out)
+    }
     pub fn plot<T>(&mut self, mut element: impl AsMut<T>)
     where
         T: GeometryAxes<X = X, Y = Y>,
@@ -56,4 +140,14 @@ impl<'a, X: AxisType, Y: AxisType> AxesContext<'a, X, Y> {
     pub fn contains(&self, point: Point2<X, Y>) -> bool {
         self.axes_bounds.contains(point)
     }
+    /// How far the plotting area is inset from `full_bounds` on each side, e.g. the label
+    /// area sizes that make a plotters chart line up with the native overlays
+    pub fn data_margins(&self) -> Edges<Pixels> {
+        Edges {
+            top: self.data_clip.top() - self.full_bounds.top(),
+            right: self.full_bounds.right() - self.data_clip.right(),
+            bottom: self.full_bounds.bottom() - self.data_clip.bottom(),
+            left: self.data_clip.left() - self.full_bounds.left(),
+        }
+    }
 }
@@ -1,10 +1,18 @@
-use crate::figure::axes::{Axes, AxesContext, AxesView};
+use crate::figure::axes::{Axes, AxesContext, AxesView, RenderBackend, Selection};
 use crate::figure::grid::GridModel;
+use crate::figure::ticks::{TickFormat, TicksView};
 use crate::geometry::{
-    AxesBounds, AxesBoundsPixels, AxisType, GeometryAxes, GeometryAxesFn, GeometryPixels, Point2,
+    AxesBounds, AxesBoundsPixels, AxisRange, AxisType, FontConfig, GeometryAxes, GeometryAxesFn,
+    GeometryPixels, Point2, Series, SeriesStats, DEFAULT_MIN_LINE_WIDTH,
+};
+use crate::utils::math::nice_step;
+use gpui::{
+    px, size, App, Bounds, Edges, Hsla, MouseMoveEvent, Pixels, Point, SharedString, Size, Window,
 };
-use gpui::{size, App, Bounds, MouseMoveEvent, Pixels, Point, Window};
 use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 
 pub(crate) struct PanState<X: AxisType, Y: AxisType> {
     initial_axes_bounds: AxesBounds<X, Y>,
@@ -15,8 +23,115 @@ pub(crate) struct ZoomState<X: AxisType, Y: AxisType> {
     pixel_bounds: AxesBoundsPixels,
     initial_zoom_position: Point<Pixels>,
     zoom_point: Point<f64>,
+    min_span: Size<f64>,
+    max_span: Size<f64>,
 }
 
+/// Limits on how far the axes can be zoomed in and out
+#[derive(Clone, Copy, Debug)]
+pub struct ZoomLimits {
+    /// The smallest span an axis can be zoomed in to
+    pub min_span: Size<f64>,
+    /// The largest span an axis can be zoomed out to, used when there is no data
+    pub max_span: Option<Size<f64>>,
+    /// How much zooming out may exceed the data extent, as a fraction of the extent
+    pub data_margin: f64,
+}
+impl Default for ZoomLimits {
+    fn default() -> Self {
+        Self {
+            min_span: size(0.0, 0.0),
+            max_span: None,
+            data_margin: 0.5,
+        }
+    }
+}
+
+/// How the borders around the plotting area are drawn, see `AxesModel::spines`
+#[derive(Clone, Copy, Debug)]
+pub struct FrameStyle {
+    pub color: Hsla,
+    pub width: Pixels,
+    /// Dash and gap lengths. Solid when `None`
+    pub dash: Option<(Pixels, Pixels)>,
+    /// Arrowheads at the far ends of the bottom and left spines, like a textbook graph
+    pub arrows: bool,
+}
+impl Default for FrameStyle {
+    fn default() -> Self {
+        Self {
+            color: gpui::black(),
+            width: px(1.0),
+            dash: None,
+            arrows: false,
+        }
+    }
+}
+impl FrameStyle {
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = width;
+        self
+    }
+    pub fn dashed(mut self, dash: Pixels, gap: Pixels) -> Self {
+        self.dash = Some((dash, gap));
+        self
+    }
+    pub fn arrows(mut self, arrows: bool) -> Self {
+        self.arrows = arrows;
+        self
+    }
+}
+
+/// Identifies an element of an `AxesModel` across reorders, removals and merges
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GeometryId(u64);
+impl GeometryId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+type Element<X, Y> = Box<dyn GeometryAxes<X = X, Y = Y>>;
+
+/// How far from an element the cursor may be for `AxesModel::highlight_nearest` to pick it
+const HIGHLIGHT_MAX_DISTANCE: Pixels = px(20.0);
+/// Number of steps targeted when snapping bounds to nice numbers
+const NICE_STEP_COUNT: f64 = 10.0;
+
+type VisibleChangedFn<X> = Box<dyn FnMut(AxisRange<X>) + Send>;
+type BrushFn<X> = Box<dyn FnMut(AxisRange<X>) + Send>;
+type ViewEventFn<X, Y> = Box<dyn FnMut(ViewEvent<X, Y>) + Send>;
+
+/// The interaction behind a `ViewEvent`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewEventKind {
+    Pan,
+    Zoom,
+    BoxZoom,
+    /// Fitted to the data by `Axes::update`
+    Reset,
+}
+/// A change of the view, in the order they happened.
+/// Replay one by passing `bounds` to `AxesModel::zoom_to` with no padding
+#[derive(Clone, Copy, Debug)]
+pub struct ViewEvent<X: AxisType, Y: AxisType> {
+    pub kind: ViewEventKind,
+    pub time: Instant,
+    /// The bounds after the change
+    pub bounds: AxesBounds<X, Y>,
+}
+
+/// How the view of an axes follows the data and the user.
+///
+/// - `update_range` and `update` (when it refits) switch to `Auto`
+/// - panning or zooming `Auto` axes switches to `Free`, and `update` leaves the view where the
+///   user put it until `update_range` fits to the data again
+/// - `freeze_autoscale` switches to `Fixed` and `unfreeze_autoscale` restores the previous type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ViewUpdateType {
     /// Freely movable
     Free,
@@ -29,12 +144,81 @@ pub enum ViewUpdateType {
 pub struct AxesModel<X: AxisType, Y: AxisType> {
     pub axes_bounds: AxesBounds<X, Y>,
     pub pixel_bounds: AxesBoundsPixels,
+    /// The whole area given to the axes, including the margins for ticks
+    pub(crate) full_bounds: Bounds<Pixels>,
     pub grid: GridModel<X, Y>,
     pub(crate) pan_state: Option<PanState<X, Y>>,
     pub(crate) zoom_state: Option<ZoomState<X, Y>>,
     pub(crate) event_processed: bool,
-    pub(crate) elements: Vec<Box<dyn GeometryAxes<X = X, Y = Y>>>,
+    pub(crate) elements: Vec<Element<X, Y>>,
+    /// The id of each element, by element index
+    element_ids: Vec<GeometryId>,
     pub update_type: ViewUpdateType,
+    /// The update type to restore on `unfreeze_autoscale`, `Some` while frozen
+    frozen_update_type: Option<ViewUpdateType>,
+    /// Set when the user pans or zooms `Auto` axes, keeps `update` from refitting
+    user_override: bool,
+    /// Overrides the rect geometry is clipped to. Defaults to the plotting area
+    pub clip_rect: Option<Bounds<Pixels>>,
+    /// Snap the bounds outward to nice round numbers once a zoom gesture ends
+    pub snap_on_zoom_end: bool,
+    /// Bounds zooming out by the data extent and zooming in by a minimum span
+    pub zoom_limits: Option<ZoomLimits>,
+    /// Zooming leaves a locked axis as it is, scaling only the other one
+    pub x_locked: bool,
+    pub y_locked: bool,
+    /// Panning and zooming stay within these bounds, like the edges of a map
+    pub navigation_bounds: Option<AxesBounds<X, Y>>,
+    /// Which of the four borders around the plotting area are drawn
+    pub spines: Edges<bool>,
+    pub frame_style: FrameStyle,
+    /// Draw axis lines through x = 0 and y = 0 whenever zero is in view
+    pub draw_origin_axes: bool,
+    pub origin_axes_style: FrameStyle,
+    /// Mark the value of each element at the hovered x
+    pub show_data_cursor: bool,
+    /// Dim every element but the one nearest the hovered position
+    pub highlight_nearest: bool,
+    /// The element nearest the hovered position, see `highlight_nearest`
+    pub(crate) highlighted: Option<usize>,
+    /// List count, min, max, mean and last of the visible points of each series in a corner
+    pub show_stats: bool,
+    pub(crate) cursor_position: Option<Point<Pixels>>,
+    /// Lines are never stroked thinner than this, so hairlines stay visible
+    pub min_line_width: Pixels,
+    /// Fit y to the data within the visible x range after every pan and zoom
+    pub y_autoscale: bool,
+    /// Half the span shown around an axis whose data all shares one value.
+    /// `None` leaves it to `AxisType::degenerate_half_span`
+    pub x_degenerate_half_span: Option<X::Delta>,
+    pub y_degenerate_half_span: Option<Y::Delta>,
+    /// The margins around the plotting area never shrink below these
+    pub min_margins: Edges<Pixels>,
+    /// Font size of the tick labels, the margins around the plotting area follow it
+    pub tick_font_size: Pixels,
+    /// Font size of the labels drawn over the plotting area, like the data cursor's
+    pub label_font_size: Pixels,
+    /// Font of all text on the axes. Falls back to the window's font when `None` or not installed
+    pub font_family: Option<SharedString>,
+    /// Tick labels wider than this are truncated with an ellipsis
+    pub tick_label_max_width: Option<Pixels>,
+    pub x_tick_format: TickFormat,
+    pub y_tick_format: TickFormat,
+    /// Make `snap_point` move points to the nearest grid intersection
+    pub snap_to_grid: bool,
+    pub(crate) selection: Option<Selection>,
+    /// Where each element painted during the last frame, by element index
+    pub(crate) painted_bounds: Vec<Bounds<Pixels>>,
+    /// Fit to the data on the first render that has any, see `new_auto`
+    pub(crate) fit_pending: bool,
+    /// Space kept free inside the plotting area on each side, as a fraction of the range.
+    /// Only affects display, the bounds and auto-fit stay as they are
+    pub view_padding: Edges<f32>,
+    pub(crate) on_visible_changed: Option<VisibleChangedFn<X>>,
+    /// The x range last reported to `on_visible_changed`
+    last_visible_x: Option<(f64, f64)>,
+    pub(crate) on_brush: Option<BrushFn<X>>,
+    pub(crate) on_view_event: Option<ViewEventFn<X, Y>>,
 }
 impl<X: AxisType, Y: AxisType> Debug for AxesModel<X, Y> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -49,79 +233,581 @@ impl<X: AxisType, Y: AxisType> AxesModel<X, Y> {
         let mut this = Self {
             axes_bounds,
             pixel_bounds: AxesBoundsPixels::from_bounds(Bounds::default()),
+            full_bounds: Bounds::default(),
             grid,
             pan_state: None,
             zoom_state: None,
             event_processed: false,
             elements: Vec::new(),
+            element_ids: Vec::new(),
             update_type: ViewUpdateType::Free,
+            frozen_update_type: None,
+            user_override: false,
+            clip_rect: None,
+            snap_on_zoom_end: false,
+            zoom_limits: None,
+            x_locked: false,
+            y_locked: false,
+            navigation_bounds: None,
+            spines: Edges {
+                top: true,
+                right: true,
+                bottom: true,
+                left: true,
+            },
+            frame_style: FrameStyle::default(),
+            draw_origin_axes: false,
+            origin_axes_style: FrameStyle::default().color(gpui::black().opacity(0.5)),
+            show_data_cursor: false,
+            highlight_nearest: false,
+            highlighted: None,
+            show_stats: false,
+            cursor_position: None,
+            min_line_width: DEFAULT_MIN_LINE_WIDTH,
+            y_autoscale: false,
+            x_degenerate_half_span: None,
+            y_degenerate_half_span: None,
+            min_margins: Edges::default(),
+            tick_font_size: px(12.0),
+            label_font_size: px(12.0),
+            font_family: None,
+            tick_label_max_width: None,
+            x_tick_format: TickFormat::default(),
+            y_tick_format: TickFormat::default(),
+            snap_to_grid: false,
+            selection: None,
+            painted_bounds: Vec::new(),
+            fit_pending: false,
+            view_padding: Edges {
+                top: 0.0,
+                right: 0.0,
+                bottom: 0.0,
+                left: 0.0,
+            },
+            on_visible_changed: None,
+            last_visible_x: None,
+            on_brush: None,
+            on_view_event: None,
         };
 
         let cx1 = AxesContext::new_without_context(&this);
         this.grid.try_update_grid(&cx1);
         this
     }
+    /// Axes without initial bounds, fitted to the data once it's there
+    pub fn new_auto(grid: GridModel<X, Y>) -> Self {
+        let placeholder = AxesBounds::new(
+            AxisRange::new(X::from_f64(0.0), X::from_f64(1.0)),
+            AxisRange::new(Y::from_f64(0.0), Y::from_f64(1.0)),
+        );
+        let mut this = Self::new(placeholder, grid);
+        this.update_type = ViewUpdateType::Auto;
+        this.fit_pending = true;
+        this
+    }
     pub fn clear_elements(&mut self) {
         self.elements.clear();
+        self.element_ids.clear();
     }
-    pub fn add_element(&mut self, element: Box<dyn GeometryAxes<X = X, Y = Y>>) {
+    /// Add `element` on top of the others, returning the id to replace or remove it by
+    pub fn add_element(&mut self, element: Element<X, Y>) -> GeometryId {
+        let id = GeometryId::next();
         self.elements.push(element);
+        self.element_ids.push(id);
+        id
+    }
+    pub fn plot(&mut self, element: impl GeometryAxes<X = X, Y = Y> + 'static) -> GeometryId {
+        self.add_element(Box::new(element))
+    }
+    pub fn plot_fn(
+        &mut self,
+        element: impl FnMut(&mut AxesContext<X, Y>) + Send + Sync + 'static,
+    ) -> GeometryId {
+        self.add_element(Box::new(GeometryAxesFn::new(element)))
+    }
+    /// The current index of the element with `id`, e.g. for `bring_to_front`
+    pub fn element_index(&self, id: GeometryId) -> Option<usize> {
+        self.element_ids.iter().position(|other| *other == id)
+    }
+    /// Replace the element with `id` in place, keeping its id and draw order.
+    /// Returns whether it was found
+    pub fn set_element(&mut self, id: GeometryId, element: Element<X, Y>) -> bool {
+        let Some(index) = self.element_index(id) else {
+            return false;
+        };
+        self.elements[index] = element;
+        if let Some(selection) = self.selection.as_mut() {
+            selection.indices.remove(&index);
+        }
+        true
+    }
+    /// Remove the element with `id`. Returns whether it was found
+    pub fn remove_element(&mut self, id: GeometryId) -> bool {
+        let Some(index) = self.element_index(id) else {
+            return false;
+        };
+        self.elements.remove(index);
+        self.element_ids.remove(index);
+        if index < self.painted_bounds.len() {
+            self.painted_bounds.remove(index);
+        }
+        let shifted = |other: usize| if other > index { other - 1 } else { other };
+        self.highlighted = self
+            .highlighted
+            .filter(|highlighted| *highlighted != index)
+            .map(shifted);
+        if let Some(selection) = self.selection.as_mut() {
+            selection.indices = std::mem::take(&mut selection.indices)
+                .into_iter()
+                .filter(|(element, _)| *element != index)
+                .map(|(element, indices)| (shifted(element), indices))
+                .collect();
+        }
+        true
+    }
+    /// Move all elements of `other` on top of these, returning the range they now take
+    pub(crate) fn append_elements(&mut self, other: &mut Self) -> Range<usize> {
+        let start = self.elements.len();
+        self.elements.append(&mut other.elements);
+        self.element_ids.append(&mut other.element_ids);
+        other.painted_bounds.clear();
+        start..self.elements.len()
+    }
+    /// Take the elements in `range`, clamped to the elements there are, with their ids
+    pub(crate) fn drain_elements(
+        &mut self,
+        range: Range<usize>,
+    ) -> Vec<(GeometryId, Element<X, Y>)> {
+        let len = self.elements.len();
+        let range = range.start.min(len)..range.end.min(len);
+        self.painted_bounds.clear();
+        let ids = self.element_ids.drain(range.clone());
+        ids.zip(self.elements.drain(range)).collect()
+    }
+    /// Put `elements` under the existing ones, keeping their ids
+    pub(crate) fn prepend_elements(&mut self, elements: Vec<(GeometryId, Element<X, Y>)>) {
+        let (ids, elements): (Vec<_>, Vec<_>) = elements.into_iter().unzip();
+        self.element_ids.splice(0..0, ids);
+        self.elements.splice(0..0, elements);
+        self.painted_bounds.clear();
+    }
+    /// Draw the element at `index` over all others, e.g. after clicking it
+    pub fn bring_to_front(&mut self, index: usize) {
+        let last = self.elements.len().saturating_sub(1);
+        self.move_element(index, last);
+    }
+    /// Draw the element at `index` under all others
+    pub fn send_to_back(&mut self, index: usize) {
+        self.move_element(index, 0);
+    }
+    /// Move the element at `from` to `to` in the draw order, shifting those in between.
+    /// Indices held by the axes, like the selection's, follow their elements.
+    /// Ranges recorded by `PlotModel::merge_axes` do not
+    fn move_element(&mut self, from: usize, to: usize) {
+        if from >= self.elements.len() || to >= self.elements.len() || from == to {
+            return;
+        }
+        let element = self.elements.remove(from);
+        self.elements.insert(to, element);
+        let id = self.element_ids.remove(from);
+        self.element_ids.insert(to, id);
+        if from < self.painted_bounds.len() && to < self.painted_bounds.len() {
+            let bounds = self.painted_bounds.remove(from);
+            self.painted_bounds.insert(to, bounds);
+        }
+        let moved = |index: usize| {
+            if index == from {
+                to
+            } else if from < index && index <= to {
+                index - 1
+            } else if to <= index && index < from {
+                index + 1
+            } else {
+                index
+            }
+        };
+        self.highlighted = self.highlighted.map(moved);
+        if let Some(selection) = self.selection.as_mut() {
+            selection.indices = std::mem::take(&mut selection.indices)
+                .into_iter()
+                .map(|(element, indices)| (moved(element), indices))
+                .collect();
+        }
     }
-    pub fn plot(&mut self, element: impl GeometryAxes<X = X, Y = Y> + 'static) {
-        self.elements.push(Box::new(element));
+
+    /// Call `f` with the visible x range after a render in which it changed,
+    /// e.g. to load more detailed data for the zoomed region
+    pub fn on_visible_changed(&mut self, f: impl FnMut(AxisRange<X>) + Send + 'static) {
+        self.on_visible_changed = Some(Box::new(f));
+        self.last_visible_x = None;
+    }
+    /// Call `f` with the brushed x range while a brush is dragged, see `PlotModel::brush_mode`
+    pub fn on_brush(&mut self, f: impl FnMut(AxisRange<X>) + Send + 'static) {
+        self.on_brush = Some(Box::new(f));
+    }
+    /// Call `f` with every pan step, zoom step, box zoom and reset, e.g. to record the navigation
+    pub fn on_view_event(&mut self, f: impl FnMut(ViewEvent<X, Y>) + Send + 'static) {
+        self.on_view_event = Some(Box::new(f));
+    }
+    fn emit_view_event(&mut self, kind: ViewEventKind) {
+        let Some(callback) = self.on_view_event.as_mut() else {
+            return;
+        };
+        callback(ViewEvent {
+            kind,
+            time: Instant::now(),
+            bounds: self.axes_bounds,
+        });
+    }
+    pub(crate) fn notify_visible_changed(&mut self) {
+        let Some(callback) = self.on_visible_changed.as_mut() else {
+            return;
+        };
+        let x = self.axes_bounds.x;
+        let visible = (x.min().to_f64(), x.max().to_f64());
+        if self.last_visible_x == Some(visible) {
+            return;
+        }
+        self.last_visible_x = Some(visible);
+        callback(x);
+    }
+    /// Highlight points of the elements, or clear the highlight with `None`
+    pub fn set_selection(&mut self, selection: Option<Selection>) {
+        self.selection = selection;
     }
-    pub fn plot_fn(&mut self, element: impl FnMut(&mut AxesContext<X, Y>) + Send + Sync + 'static) {
-        self.elements.push(Box::new(GeometryAxesFn::new(element)));
+    pub fn selection(&self) -> Option<&Selection> {
+        self.selection.as_ref()
+    }
+    /// Space around the plotting area: what the tick labels need, at least `min_margins`
+    pub fn margins(&self, window: &Window) -> Edges<Pixels> {
+        let needed = TicksView::new(self).margins(window);
+        let min = self.min_margins;
+        Edges {
+            top: px(needed.top.0.max(min.top.0)),
+            right: px(needed.right.0.max(min.right.0)),
+            bottom: px(needed.bottom.0.max(min.bottom.0)),
+            left: px(needed.left.0.max(min.left.0)),
+        }
+    }
+    /// Only the bottom and left spines, ending in arrowheads
+    pub fn set_open_frame(&mut self) {
+        self.spines = Edges {
+            top: false,
+            right: false,
+            bottom: true,
+            left: true,
+        };
+        self.frame_style.arrows = true;
+    }
+    /// Show `bounds`, grown by `padding` as a fraction of its size, e.g. `0.1` for 10%
+    /// A single point, or points sharing an x or y, are widened like single-value data
+    pub fn zoom_to(&mut self, mut bounds: AxesBounds<X, Y>, padding: f64) {
+        if padding != 0.0 {
+            bounds.resize(1.0 + padding);
+        }
+        bounds.x = widen_degenerate(bounds.x, self.x_degenerate_half_span);
+        bounds.y = widen_degenerate(bounds.y, self.y_degenerate_half_span);
+        self.take_over_from_auto();
+        self.axes_bounds = bounds;
+        self.rescale();
+        self.try_update_grid();
+    }
+    /// The bounds of the selected points, if any of them exist
+    pub fn selection_bounds(&self) -> Option<AxesBounds<X, Y>> {
+        let selection = self.selection.as_ref()?;
+        let mut extent: Option<(Point2<X, Y>, Point2<X, Y>)> = None;
+        for (&element, indices) in selection.indices.iter() {
+            let Some(element) = self.elements.get(element) else {
+                continue;
+            };
+            for point in indices.iter().filter_map(|&index| element.point_at(index)) {
+                extent = Some(match extent {
+                    None => (point, point),
+                    Some((min, max)) => (
+                        Point2::new(
+                            if point.x < min.x { point.x } else { min.x },
+                            if point.y < min.y { point.y } else { min.y },
+                        ),
+                        Point2::new(
+                            if point.x > max.x { point.x } else { max.x },
+                            if point.y > max.y { point.y } else { max.y },
+                        ),
+                    ),
+                });
+            }
+        }
+        let (min, max) = extent?;
+        Some(AxesBounds::new(
+            AxisRange::new(min.x, max.x),
+            AxisRange::new(min.y, max.y),
+        ))
+    }
+    /// Zoom to the selected points, see `zoom_to`. Returns whether there was anything to zoom to
+    pub fn zoom_to_selection(&mut self, padding: f64) -> bool {
+        let Some(bounds) = self.selection_bounds() else {
+            return false;
+        };
+        self.zoom_to(bounds, padding);
+        true
+    }
+    /// Where the element at `index` painted during the last frame.
+    /// Elements that don't report it are taken to cover the whole plotting area
+    pub fn painted_bounds(&self, index: usize) -> Option<Bounds<Pixels>> {
+        self.painted_bounds.get(index).copied()
     }
 
-    pub fn update_scale(&mut self, shrunk_bounds: Bounds<Pixels>) {
+    /// Lay the axes out over `full_bounds`, with the plotting area at `shrunk_bounds`
+    pub fn update_scale(&mut self, full_bounds: Bounds<Pixels>, shrunk_bounds: Bounds<Pixels>) {
+        self.full_bounds = full_bounds;
         self.pixel_bounds = AxesBoundsPixels::from_bounds(shrunk_bounds);
+        let padding = self.view_padding;
+        self.pixel_bounds
+            .x
+            .inset(padding.left as f64, padding.right as f64);
+        self.pixel_bounds
+            .y
+            .inset(padding.bottom as f64, padding.top as f64);
         self.pixel_bounds.x.pixels_per_element =
             self.axes_bounds.x.pixels_per_element(self.pixel_bounds.x);
         self.pixel_bounds.y.pixels_per_element =
             -self.axes_bounds.y.pixels_per_element(self.pixel_bounds.y);
     }
+    /// Redo `update_scale` with the current layout, after the axes bounds changed
+    fn rescale(&mut self) {
+        self.update_scale(self.full_bounds, self.pixel_bounds.into_bounds());
+    }
     pub fn transform_point(&self, point: Point2<X, Y>) -> Point<Pixels> {
         self.axes_bounds.transform_point(self.pixel_bounds, point)
     }
+    /// Move `point` to the nearest grid intersection when `snap_to_grid` is set.
+    /// Axes without grid lines are left as they are
+    pub fn snap_point(&self, point: Point2<X, Y>) -> Point2<X, Y> {
+        if !self.snap_to_grid {
+            return point;
+        }
+        Point2::new(
+            nearest(&self.grid.grid_x_lines, point.x).unwrap_or(point.x),
+            nearest(&self.grid.grid_y_lines, point.y).unwrap_or(point.y),
+        )
+    }
+    /// The current view as `pixel = data * scale + offset` per axis, in `to_f64` data units.
+    /// Returns `(scale_x, offset_x, scale_y, offset_y)`
+    pub fn data_to_pixel_transform(&self) -> (f64, f64, f64, f64) {
+        let (scale_x, offset_x) = self.axes_bounds.x.linear_transform(self.pixel_bounds.x);
+        let (scale_y, offset_y) = self.axes_bounds.y.linear_transform(self.pixel_bounds.y);
+        (scale_x, offset_x, scale_y, offset_y)
+    }
+    /// The inverse of `data_to_pixel_transform`, as `data = pixel * scale + offset`
+    pub fn pixel_to_data_transform(&self) -> (f64, f64, f64, f64) {
+        let (scale_x, offset_x, scale_y, offset_y) = self.data_to_pixel_transform();
+        (
+            1.0 / scale_x,
+            -offset_x / scale_x,
+            1.0 / scale_y,
+            -offset_y / scale_y,
+        )
+    }
+    /// Move the view so that it matches `pixel = data * scale + offset` per axis
+    pub fn set_data_to_pixel_transform(
+        &mut self,
+        (scale_x, offset_x, scale_y, offset_y): (f64, f64, f64, f64),
+    ) {
+        self.axes_bounds
+            .x
+            .set_linear_transform(self.pixel_bounds.x, scale_x, offset_x);
+        self.axes_bounds
+            .y
+            .set_linear_transform(self.pixel_bounds.y, scale_y, offset_y);
+        self.rescale();
+        self.try_update_grid();
+    }
+    /// The plotting area, inside the margins
+    pub fn data_clip(&self) -> Bounds<Pixels> {
+        self.pixel_bounds.into_bounds()
+    }
+    /// Where the plotting area was laid out in the window during the last render,
+    /// e.g. to anchor overlays to it. `full_bounds` minus the margins
+    pub fn interior_bounds(&self) -> Bounds<Pixels> {
+        self.data_clip()
+    }
+    /// The whole area of the axes, including the margins
+    pub fn full_bounds(&self) -> Bounds<Pixels> {
+        self.full_bounds
+    }
+    /// The rect geometry is clipped to: the override if set, otherwise the plotting area
+    pub fn clip_rect(&self) -> Bounds<Pixels> {
+        self.clip_rect
+            .unwrap_or_else(|| self.pixel_bounds.into_bounds())
+    }
 
+    /// The union of the ranges of all elements, without padding.
+    /// Each axis is unioned on its own, so elements may contribute to only one of them
+    /// Zero spans are kept, e.g. for single-value data, see `widen_degenerate`
+    pub fn data_extent(&self) -> Option<AxesBounds<X, Y>> {
+        let mut x_extent: Option<AxisRange<X>> = None;
+        let mut y_extent: Option<AxisRange<Y>> = None;
+        for element in self.elements.iter() {
+            if let Some(x) = element.get_x_range() {
+                x_extent = match x_extent {
+                    None => Some(x),
+                    Some(extent) => Some(extent.union(&x).unwrap_or(extent)),
+                };
+            }
+            if let Some(y) = element.get_y_range() {
+                y_extent = match y_extent {
+                    None => Some(y),
+                    Some(extent) => Some(extent.union(&y).unwrap_or(extent)),
+                };
+            }
+        }
+        Some(AxesBounds::new(x_extent?, y_extent?))
+    }
+    /// Hold the view where it is, e.g. while reading a live plot: `update` stops refitting
+    /// and panning is disabled
+    pub fn freeze_autoscale(&mut self) {
+        if self.frozen_update_type.is_none() {
+            self.frozen_update_type = Some(self.update_type);
+            self.update_type = ViewUpdateType::Fixed;
+        }
+    }
+    /// Restore the update type from before `freeze_autoscale`
+    pub fn unfreeze_autoscale(&mut self) {
+        if let Some(update_type) = self.frozen_update_type.take() {
+            self.update_type = update_type;
+        }
+    }
+    pub fn is_autoscale_frozen(&self) -> bool {
+        self.frozen_update_type.is_some()
+    }
+    /// Fit to the data unless the view is fixed or the user took over from auto-fitting.
+    /// Returns whether it refitted
+    pub fn auto_update(&mut self) -> bool {
+        if matches!(self.update_type, ViewUpdateType::Fixed) || self.user_override {
+            return false;
+        }
+        self.update_range();
+        true
+    }
+    /// Let the user's pan or zoom win over auto-fitting, until `update_range`
+    fn take_over_from_auto(&mut self) {
+        if matches!(self.update_type, ViewUpdateType::Auto) {
+            self.update_type = ViewUpdateType::Free;
+            self.user_override = true;
+        }
+    }
     pub fn update_range(&mut self) {
         self.update_type = ViewUpdateType::Auto;
+        self.user_override = false;
         // update the axes bounds
-        let mut new_axes_bounds = None;
+        let Some(mut new_axes_bounds) = self.data_extent() else {
+            return;
+        };
+        new_axes_bounds.resize(1.1);
+        new_axes_bounds.x = widen_degenerate(new_axes_bounds.x, self.x_degenerate_half_span);
+        new_axes_bounds.y = widen_degenerate(new_axes_bounds.y, self.y_degenerate_half_span);
+        self.axes_bounds = new_axes_bounds;
+        self.fit_pending = false;
+    }
+    /// Fit the y range to the points whose x is currently visible, leaving x untouched
+    pub fn autoscale_y_to_visible(&mut self) {
+        let x_range = self.axes_bounds.x;
+        let mut extent: Option<(Y, Y)> = None;
+        for element in self.elements.iter() {
+            element.visit_points_in(&x_range, &mut |point| {
+                extent = Some(match extent {
+                    None => (point.y, point.y),
+                    Some((min, max)) => (
+                        if point.y < min { point.y } else { min },
+                        if point.y > max { point.y } else { max },
+                    ),
+                });
+            });
+        }
+        let Some((min, max)) = extent else {
+            return;
+        };
+        let mut y = AxisRange::new(min, max);
+        y.resize(1.1);
+        self.axes_bounds.y = widen_degenerate(y, self.y_degenerate_half_span);
+        self.rescale();
+        if self.pan_state.is_none() || self.grid.regenerate_while_panning {
+            self.try_update_grid();
+        }
+    }
+    /// Stats of the points within the visible x range, with the label of each element having any
+    pub fn visible_stats(&mut self) -> Vec<(Option<String>, SeriesStats<Y>)> {
+        let x_range = self.axes_bounds.x;
+        let mut stats = vec![];
         for element in self.elements.iter_mut() {
-            let Some(x) = element.get_x_range() else {
+            let Some(element_stats) = element.visible_stats(&x_range) else {
                 continue;
             };
-            if x.size_in_f64() < 1e-6 {
-                continue;
-            }
-            let Some(y) = element.get_y_range() else {
+            let label = element
+                .as_series_mut()
+                .and_then(|series| series.label().map(String::from));
+            stats.push((label, element_stats));
+        }
+        stats
+    }
+    /// The element whose value at the x of `position` is closest to it, within `HIGHLIGHT_MAX_DISTANCE`
+    pub fn nearest_element(&self, position: Point<Pixels>) -> Option<usize> {
+        if !self.pixel_bounds.into_bounds().contains(&position) {
+            return None;
+        }
+        let x = self
+            .axes_bounds
+            .x
+            .transform_reverse(self.pixel_bounds.x, position.x);
+        let mut nearest = None;
+        let mut nearest_distance = HIGHLIGHT_MAX_DISTANCE.0;
+        for (index, element) in self.elements.iter().enumerate() {
+            let Some(y) = element.value_at_x(x) else {
                 continue;
             };
-            if y.size_in_f64() < 1e-6 {
-                continue;
+            let y = self.axes_bounds.y.transform(self.pixel_bounds.y, y);
+            let distance = (y - position.y).0.abs();
+            if distance <= nearest_distance {
+                nearest = Some(index);
+                nearest_distance = distance;
             }
-            match new_axes_bounds {
-                None => {
-                    new_axes_bounds = Some(AxesBounds::new(x, y));
-                }
-                Some(ref mut bounds) => {
-                    if let Some(x_union) = bounds.x.union(&x) {
-                        bounds.x = x_union;
-                    }
-                    if let Some(y_union) = bounds.y.union(&y) {
-                        bounds.y = y_union;
+        }
+        nearest
+    }
+    /// The (min, max) span each axis may be zoomed to
+    fn zoom_span_limits(&self) -> (Size<f64>, Size<f64>) {
+        let unlimited = size(f64::INFINITY, f64::INFINITY);
+        let (min_span, mut max_span) = match self.zoom_limits {
+            None => (size(0.0, 0.0), unlimited),
+            Some(limits) => {
+                let max_span = match self.data_extent() {
+                    // single-value data may zoom out as far as its fit shows it
+                    Some(mut extent) => {
+                        extent.x = widen_degenerate(extent.x, self.x_degenerate_half_span);
+                        extent.y = widen_degenerate(extent.y, self.y_degenerate_half_span);
+                        size(
+                            extent.x.size_in_f64() * (1.0 + limits.data_margin),
+                            extent.y.size_in_f64() * (1.0 + limits.data_margin),
+                        )
                     }
-                }
+                    None => limits.max_span.unwrap_or(unlimited),
+                };
+                (limits.min_span, max_span)
             }
+        };
+        if let Some(bounds) = self.navigation_bounds {
+            max_span.width = max_span.width.min(bounds.x.size_in_f64().abs());
+            max_span.height = max_span.height.min(bounds.y.size_in_f64().abs());
         }
-
-        let Some(mut new_pixel_bounds) = new_axes_bounds else {
+        (min_span, max_span)
+    }
+    /// Move the view back inside `navigation_bounds`, shrinking it if it's larger
+    fn contain_in_navigation_bounds(&mut self) {
+        let Some(bounds) = self.navigation_bounds else {
             return;
         };
-        new_pixel_bounds.resize(1.1);
-        self.axes_bounds = new_pixel_bounds;
+        self.axes_bounds.x = contain_range(self.axes_bounds.x, bounds.x);
+        self.axes_bounds.y = contain_range(self.axes_bounds.y, bounds.y);
+        self.rescale();
     }
     pub fn update_grid(&mut self) {
         let cx1 = AxesContext::new_without_context(self);
@@ -131,16 +817,33 @@ impl<X: AxisType, Y: AxisType> AxesModel<X, Y> {
         let cx1 = AxesContext::new_without_context(self);
         self.grid.try_update_grid(&cx1);
     }
+    /// Expand the bounds outward to the nearest nice round numbers
+    pub fn snap_to_nice_bounds(&mut self) {
+        let x_step = nice_step(self.axes_bounds.x.size_in_f64() / NICE_STEP_COUNT);
+        let y_step = nice_step(self.axes_bounds.y.size_in_f64() / NICE_STEP_COUNT);
+        self.axes_bounds.x.snap_outward(x_step);
+        self.axes_bounds.y.snap_outward(y_step);
+        self.try_update_grid();
+    }
 }
 
 impl<X: AxisType, Y: AxisType> Axes for AxesModel<X, Y> {
+    fn backend(&self) -> RenderBackend {
+        RenderBackend::Native
+    }
     fn update(&mut self) {
-        self.update_range();
+        if !self.auto_update() {
+            return;
+        }
         self.update_grid();
+        self.emit_view_event(ViewEventKind::Reset);
     }
 
     fn new_render(&mut self) {
         self.event_processed = false;
+        if self.fit_pending && self.data_extent().is_some() {
+            self.update();
+        }
         self.try_update_grid();
     }
     fn pan_begin(&mut self, position: Point<Pixels>) {
@@ -173,21 +876,33 @@ impl<X: AxisType, Y: AxisType> Axes for AxesModel<X, Y> {
                 .elements_per_pixels(delta_pixels.y, self.pixel_bounds.y),
         );
         self.axes_bounds = pan_state.initial_axes_bounds + delta_elements;
+        self.take_over_from_auto();
+        self.contain_in_navigation_bounds();
+        if self.y_autoscale {
+            self.autoscale_y_to_visible();
+        }
 
-        let cx1 = AxesContext::new_without_context(self);
-        self.grid.try_update_grid(&cx1);
+        if self.grid.regenerate_while_panning {
+            let cx1 = AxesContext::new_without_context(self);
+            self.grid.try_update_grid(&cx1);
+        }
+        self.emit_view_event(ViewEventKind::Pan);
     }
 
     fn pan_end(&mut self) {
         if self.event_processed {
             return;
         }
-        self.pan_state = None;
+        // catch up on the updates skipped during the pan
+        if self.pan_state.take().is_some() && !self.grid.regenerate_while_panning {
+            self.try_update_grid();
+        }
     }
     fn zoom_begin(&mut self, position: Point<Pixels>) {
         if self.event_processed {
             return;
         }
+        let (min_span, max_span) = self.zoom_span_limits();
         self.zoom_state = Some(ZoomState {
             initial_axes_bounds: self.axes_bounds,
             pixel_bounds: self.pixel_bounds,
@@ -195,34 +910,56 @@ impl<X: AxisType, Y: AxisType> Axes for AxesModel<X, Y> {
             zoom_point: self
                 .axes_bounds
                 .transform_point_reverse_f64(self.pixel_bounds, position),
+            min_span,
+            max_span,
         });
     }
     fn zoom(&mut self, factor: f64) {
+        self.zoom_xy(factor, factor);
+    }
+    fn zoom_xy(&mut self, factor_x: f64, factor_y: f64) {
         if self.event_processed {
             return;
         }
-        let Some(zoom_state) = &mut self.zoom_state else {
+        let Some(zoom_state) = &self.zoom_state else {
             return;
         };
         let zoom_point = zoom_state.zoom_point;
-        let zoom_factor = factor;
+        let initial = zoom_state.initial_axes_bounds;
+        // a factor of 1 keeps a locked axis, and its part of the zoom point, where it was
+        let factor_x = if self.x_locked {
+            1.0
+        } else {
+            clamp_zoom_factor(
+                initial.x.size_in_f64(),
+                factor_x,
+                zoom_state.min_span.width,
+                zoom_state.max_span.width,
+            )
+        };
+        let factor_y = if self.y_locked {
+            1.0
+        } else {
+            clamp_zoom_factor(
+                initial.y.size_in_f64(),
+                factor_y,
+                zoom_state.min_span.height,
+                zoom_state.max_span.height,
+            )
+        };
 
         self.axes_bounds.x.min_to_base =
-            (zoom_state.initial_axes_bounds.x.min_to_base - zoom_point.x) * zoom_factor
-                + zoom_point.x;
+            (initial.x.min_to_base - zoom_point.x) * factor_x + zoom_point.x;
         self.axes_bounds.x.max_to_base =
-            (zoom_state.initial_axes_bounds.x.max_to_base - zoom_point.x) * zoom_factor
-                + zoom_point.x;
+            (initial.x.max_to_base - zoom_point.x) * factor_x + zoom_point.x;
         self.axes_bounds.y.min_to_base =
-            (zoom_state.initial_axes_bounds.y.min_to_base - zoom_point.y) * zoom_factor
-                + zoom_point.y;
+            (initial.y.min_to_base - zoom_point.y) * factor_y + zoom_point.y;
         self.axes_bounds.y.max_to_base =
-            (zoom_state.initial_axes_bounds.y.max_to_base - zoom_point.y) * zoom_factor
-                + zoom_point.y;
+            (initial.y.max_to_base - zoom_point.y) * factor_y + zoom_point.y;
         self.pixel_bounds.x.pixels_per_element =
-            zoom_state.pixel_bounds.x.pixels_per_element / zoom_factor;
+            zoom_state.pixel_bounds.x.pixels_per_element / factor_x;
         self.pixel_bounds.y.pixels_per_element =
-            zoom_state.pixel_bounds.y.pixels_per_element / zoom_factor;
+            zoom_state.pixel_bounds.y.pixels_per_element / factor_y;
         let afterwards_zoom_point = self
             .axes_bounds
             .transform_point_reverse_f64(self.pixel_bounds, zoom_state.initial_zoom_position);
@@ -231,6 +968,11 @@ impl<X: AxisType, Y: AxisType> Axes for AxesModel<X, Y> {
         self.axes_bounds.x.max_to_base += diff.x;
         self.axes_bounds.y.min_to_base += diff.y;
         self.axes_bounds.y.max_to_base += diff.y;
+        self.take_over_from_auto();
+        self.contain_in_navigation_bounds();
+        if self.y_autoscale {
+            self.autoscale_y_to_visible();
+        }
         // let adjusted_zoom_point = self
         //     .axes_bounds
         //     .transform_point_reverse_f64(self.pixel_bounds, zoom_state.initial_zoom_position);
@@ -239,16 +981,251 @@ impl<X: AxisType, Y: AxisType> Axes for AxesModel<X, Y> {
 
         let cx1 = AxesContext::new_without_context(self);
         self.grid.try_update_grid(&cx1);
+        self.emit_view_event(ViewEventKind::Zoom);
     }
 
     fn zoom_end(&mut self) {
         if self.event_processed {
             return;
         }
-        self.zoom_state = None;
+        // only snap after the gesture, so the cursor-stable zoom isn't disturbed
+        if self.zoom_state.take().is_some() && self.snap_on_zoom_end {
+            self.snap_to_nice_bounds();
+        }
+    }
+    fn zoom_to_pixel_bounds(&mut self, bounds: Bounds<Pixels>) {
+        if matches!(self.update_type, ViewUpdateType::Fixed) {
+            return;
+        }
+        let x = self.axes_bounds.x;
+        let y = self.axes_bounds.y;
+        if !self.x_locked {
+            self.axes_bounds.x = AxisRange::new(
+                x.transform_reverse(self.pixel_bounds.x, bounds.left()),
+                x.transform_reverse(self.pixel_bounds.x, bounds.right()),
+            );
+        }
+        if !self.y_locked {
+            self.axes_bounds.y = AxisRange::new(
+                y.transform_reverse(self.pixel_bounds.y, bounds.bottom()),
+                y.transform_reverse(self.pixel_bounds.y, bounds.top()),
+            );
+        }
+        self.take_over_from_auto();
+        self.rescale();
+        self.try_update_grid();
+        self.emit_view_event(ViewEventKind::BoxZoom);
+    }
+    fn hover(&mut self, position: Option<Point<Pixels>>) -> bool {
+        let position = position.filter(|_| self.show_data_cursor || self.highlight_nearest);
+        let highlighted = match position {
+            Some(position) if self.highlight_nearest => self.nearest_element(position),
+            _ => None,
+        };
+        // only the data cursor follows every move, the highlight changes between elements
+        let changed = highlighted != self.highlighted
+            || (self.show_data_cursor && position != self.cursor_position);
+        self.cursor_position = position;
+        self.highlighted = highlighted;
+        changed
+    }
+    fn add_point_at(&mut self, element: usize, position: Point<Pixels>) -> bool {
+        if !self.data_clip().contains(&position) {
+            return false;
+        }
+        let point = self
+            .axes_bounds
+            .transform_point_reverse(self.pixel_bounds, position);
+        let point = self.snap_point(point);
+        match self.elements.get_mut(element) {
+            Some(element) => element.push_point(point),
+            None => false,
+        }
+    }
+    fn margins(&self, window: &Window) -> Edges<Pixels> {
+        AxesModel::margins(self, window)
+    }
+    fn set_font(&mut self, font: &FontConfig) {
+        self.tick_font_size = font.tick_size;
+        self.label_font_size = font.label_size;
+        self.font_family = font.family.clone();
+    }
+    fn min_margins(&self) -> Edges<Pixels> {
+        self.min_margins
+    }
+    fn set_min_margins(&mut self, margins: Edges<Pixels>) {
+        self.min_margins = margins;
+    }
+    fn brush(&mut self, start: Pixels, end: Pixels) {
+        let x = self.axes_bounds.x;
+        let start = x.transform_reverse(self.pixel_bounds.x, start);
+        let end = x.transform_reverse(self.pixel_bounds.x, end);
+        let range = if start <= end {
+            AxisRange::new(start, end)
+        } else {
+            AxisRange::new(end, start)
+        };
+        if let Some(callback) = self.on_brush.as_mut() {
+            callback(range);
+        }
     }
 
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series)) {
+        for element in self.elements.iter_mut() {
+            element.for_each_series(f);
+        }
+    }
     fn render(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         AxesView::new(self).render_pixels(bounds, window, cx);
+        self.notify_visible_changed();
+    }
+}
+
+/// The value in `values` closest to `value`
+fn nearest<T: AxisType>(values: &[T], value: T) -> Option<T> {
+    let value = value.to_f64();
+    values.iter().copied().min_by(|a, b| {
+        let da = (a.to_f64() - value).abs();
+        let db = (b.to_f64() - value).abs();
+        da.total_cmp(&db)
+    })
+}
+
+/// A zero-span `range`, e.g. from points that all share one value, widened around that value by
+/// `half_span` either way, so it is centered in view instead of collapsing
+fn widen_degenerate<T: AxisType>(
+    mut range: AxisRange<T>,
+    half_span: Option<T::Delta>,
+) -> AxisRange<T> {
+    // only spans lost in the rounding of the values themselves count as zero
+    let magnitude = range.min().to_f64().abs().max(range.max().to_f64().abs());
+    if range.size_in_f64().abs() > magnitude * f64::EPSILON * 4.0 {
+        return range;
+    }
+    let middle = (range.min_to_base + range.max_to_base) / 2.0;
+    let half = match half_span {
+        Some(half_span) => half_span.to_f64().abs(),
+        None => range.center().degenerate_half_span(),
+    };
+    range.min_to_base = middle - half;
+    range.max_to_base = middle + half;
+    range
+}
+/// `range` shifted to lie within `outer`, or `outer` itself if `range` doesn't fit
+fn contain_range<T: AxisType>(range: AxisRange<T>, outer: AxisRange<T>) -> AxisRange<T> {
+    let base = outer.base;
+    let low = outer.min_to_base.min(outer.max_to_base);
+    let high = outer.min_to_base.max(outer.max_to_base);
+    let min = (range.min() - base).to_f64();
+    let max = (range.max() - base).to_f64();
+    if (max - min).abs() >= high - low {
+        return outer;
+    }
+    let shift = if min.min(max) < low {
+        low - min.min(max)
+    } else if min.max(max) > high {
+        high - min.max(max)
+    } else {
+        0.0
+    };
+    AxisRange::new_with_base_f64(base, min + shift, max + shift)
+}
+
+/// Restrict a zoom factor so that `span * factor` stays within `[min_span, max_span]`
+fn clamp_zoom_factor(span: f64, factor: f64, min_span: f64, max_span: f64) -> f64 {
+    let span = span.abs();
+    if span == 0.0 {
+        return factor;
+    }
+    (span * factor).clamp(min_span, max_span.max(min_span)) / span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{point2, Line};
+
+    fn fitted<X: AxisType, Y: AxisType>(points: &[(X, Y)]) -> AxesModel<X, Y> {
+        let mut line = Line::new();
+        for &(x, y) in points {
+            line.add_point(point2(x, y));
+        }
+        let mut model = AxesModel::new_auto(GridModel::from_numbers(5, 5));
+        model.plot(line);
+        model.update_range();
+        model
+    }
+
+    #[test]
+    fn identical_y_is_centered() {
+        let model = fitted(&[(0.0, 3.0), (1.0, 3.0), (2.0, 3.0)]);
+        let y = model.axes_bounds.y;
+        assert!(y.min().is_finite() && y.max().is_finite());
+        assert!(y.size_in_f64() > 0.0);
+        assert_eq!(y.center(), 3.0);
+        assert!(y.min() < 3.0 && y.max() > 3.0);
+    }
+
+    #[test]
+    fn tiny_span_is_kept() {
+        let model = fitted(&[(0.0, 1.0), (1.0, 1.0 + 1e-9)]);
+        assert!(model.axes_bounds.y.size_in_f64() < 1e-8);
+    }
+
+    #[test]
+    fn degenerate_half_span_is_configurable() {
+        let mut model = fitted(&[(0.0, 3.0), (1.0, 3.0)]);
+        model.y_degenerate_half_span = Some(2.0);
+        model.update_range();
+        assert_eq!(model.axes_bounds.y.min(), 1.0);
+        assert_eq!(model.axes_bounds.y.max(), 5.0);
+    }
+
+    #[test]
+    fn zoom_to_a_single_point_stays_finite() {
+        let mut model = fitted(&[(0.0, 0.0), (10.0, 10.0)]);
+        model.zoom_to(
+            AxesBounds::new(AxisRange::new(4.0, 4.0), AxisRange::new(6.0, 6.0)),
+            0.1,
+        );
+        let bounds = model.axes_bounds;
+        assert_eq!((bounds.x.center(), bounds.y.center()), (4.0, 6.0));
+        assert!(bounds.x.size_in_f64() > 0.0 && bounds.y.size_in_f64() > 0.0);
+    }
+
+    #[test]
+    fn zoom_to_survives_auto_update() {
+        let mut model = fitted(&[(0.0, 0.0), (10.0, 10.0)]);
+        let zoomed = AxesBounds::new(AxisRange::new(2.0, 3.0), AxisRange::new(2.0, 3.0));
+        model.zoom_to(zoomed, 0.0);
+        assert!(!model.auto_update());
+        assert_eq!(model.axes_bounds.x.min(), 2.0);
+        assert_eq!(model.axes_bounds.x.max(), 3.0);
+    }
+
+    #[test]
+    fn identical_dates_span_seconds() {
+        let t = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let model = fitted(&[(t, 1.0), (t, 2.0)]);
+        let x = model.axes_bounds.x;
+        assert_eq!(x.center(), t);
+        assert_eq!(x.span(), chrono::Duration::seconds(2));
+    }
+
+    #[test]
+    fn identical_times_and_durations_span_seconds() {
+        let noon = chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let model = fitted(&[(noon, 1.0), (noon, 2.0)]);
+        let x = model.axes_bounds.x;
+        assert_eq!(
+            x.min(),
+            chrono::NaiveTime::from_hms_opt(11, 59, 59).unwrap()
+        );
+        assert_eq!(x.max(), chrono::NaiveTime::from_hms_opt(12, 0, 1).unwrap());
+
+        let minute = chrono::Duration::minutes(1);
+        let model = fitted(&[(1.0, minute), (2.0, minute)]);
+        assert_eq!(model.axes_bounds.y.center(), minute);
+        assert_eq!(model.axes_bounds.y.span(), chrono::Duration::seconds(2));
     }
 }
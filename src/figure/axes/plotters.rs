@@ -1,5 +1,6 @@
-use crate::figure::axes::{Axes, AxesContext, AxesModel};
-use crate::geometry::{AxisType, GeometryPixels};
+use crate::figure::axes::{Axes, AxesContext, AxesModel, RenderBackend};
+use crate::figure::grid::GridView;
+use crate::geometry::{AxisType, FontConfig, GeometryAxes, GeometryPixels, Series};
 use gpui::{px, App, Bounds, Edges, MouseMoveEvent, Pixels, Point, Window};
 use parking_lot::RwLock;
 use plotters::coord::Shift;
@@ -13,6 +14,10 @@ type ChartFn<X, Y> = Box<dyn FnMut(&mut DrawingArea<GpuiBackend, Shift>, &mut Ax
 pub struct PlottersModel<X: AxisType, Y: AxisType> {
     pub backend_color: RGBColor,
     pub chart: ChartFn<X, Y>,
+    /// Draw the native grid behind the chart, following pan and zoom
+    pub draw_grid: bool,
+    /// Draw the elements of the model over the chart, so one axes entry renders both
+    pub draw_elements: bool,
     model: Arc<RwLock<AxesModel<X, Y>>>,
 }
 impl<X: AxisType, Y: AxisType> PlottersModel<X, Y> {
@@ -20,13 +25,26 @@ impl<X: AxisType, Y: AxisType> PlottersModel<X, Y> {
         Self {
             backend_color: RGBColor(0, 0, 0),
             chart,
+            draw_grid: false,
+            draw_elements: false,
             model,
         }
     }
+    pub fn draw_grid(mut self, draw_grid: bool) -> Self {
+        self.draw_grid = draw_grid;
+        self
+    }
+    pub fn draw_elements(mut self, draw_elements: bool) -> Self {
+        self.draw_elements = draw_elements;
+        self
+    }
 }
 impl<X: AxisType, Y: AxisType> Axes for PlottersModel<X, Y> {
+    fn backend(&self) -> RenderBackend {
+        RenderBackend::Plotters
+    }
     fn update(&mut self) {
-        self.model.write().update_range();
+        self.model.write().auto_update();
     }
 
     fn new_render(&mut self) {
@@ -50,12 +68,93 @@ impl<X: AxisType, Y: AxisType> Axes for PlottersModel<X, Y> {
     fn zoom(&mut self, delta: f64) {
         self.model.write().zoom(delta);
     }
+    fn zoom_xy(&mut self, factor_x: f64, factor_y: f64) {
+        self.model.write().zoom_xy(factor_x, factor_y);
+    }
     fn zoom_end(&mut self) {
         self.model.write().zoom_end();
     }
+    fn zoom_to_pixel_bounds(&mut self, bounds: Bounds<Pixels>) {
+        self.model.write().zoom_to_pixel_bounds(bounds);
+    }
+    fn hover(&mut self, position: Option<Point<Pixels>>) -> bool {
+        self.model.write().hover(position)
+    }
+    fn add_point_at(&mut self, element: usize, position: Point<Pixels>) -> bool {
+        self.model.write().add_point_at(element, position)
+    }
+    fn margins(&self, _window: &Window) -> Edges<Pixels> {
+        Edges {
+            top: px(0.0),
+            right: CONTENT_BOARDER,
+            bottom: CONTENT_BOARDER,
+            left: CONTENT_BOARDER,
+        }
+    }
+    fn min_margins(&self) -> Edges<Pixels> {
+        Edges::default()
+    }
+    /// Plotters lays out its own margins, so this has no effect
+    fn set_min_margins(&mut self, _margins: Edges<Pixels>) {}
+    /// Only reaches the native grid and elements, plotters styles its own text
+    fn set_font(&mut self, font: &FontConfig) {
+        self.model.write().set_font(font);
+    }
+    fn brush(&mut self, start: Pixels, end: Pixels) {
+        self.model.write().brush(start, end);
+    }
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series)) {
+        self.model.write().for_each_series(f);
+    }
     fn render(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         PlottersView::new(self).render_pixels(bounds, window, cx);
+        self.model.write().notify_visible_changed();
+    }
+}
+/// Where each row but the first starts, for rows of `total` height split by `weights`
+fn row_breaks(total: f32, weights: &[f32]) -> Vec<f32> {
+    let sum: f32 = weights.iter().map(|weight| weight.max(0.0)).sum();
+    if sum <= 0.0 {
+        return vec![];
+    }
+    let mut offset = 0.0;
+    let mut breaks = vec![];
+    for weight in weights[..weights.len() - 1].iter() {
+        offset += total * weight.max(0.0) / sum;
+        breaks.push(offset);
+    }
+    breaks
+}
+/// Split `bounds` into rows whose heights follow `weights`, top to bottom.
+/// These line up with the areas of `split_rows` on a chart of the same bounds,
+/// e.g. `cx.full_bounds`, so native overlays can follow each panel
+pub fn proportional_rows(bounds: Bounds<Pixels>, weights: &[f32]) -> Vec<Bounds<Pixels>> {
+    let mut tops = vec![bounds.top()];
+    for offset in row_breaks(bounds.size.height.0, weights) {
+        tops.push(bounds.top() + px(offset.round()));
     }
+    tops.push(bounds.bottom());
+    tops.windows(2)
+        .map(|pair| {
+            Bounds::from_corners(
+                gpui::point(bounds.left(), pair[0]),
+                gpui::point(bounds.right(), pair[1]),
+            )
+        })
+        .collect()
+}
+/// Split the chart `area` into rows whose heights follow `weights`, top to bottom,
+/// e.g. `&[3.0, 1.0]` for a main chart over an indicator a third of its height
+pub fn split_rows(
+    area: &DrawingArea<GpuiBackend, Shift>,
+    weights: &[f32],
+) -> Vec<DrawingArea<GpuiBackend, Shift>> {
+    let height = area.dim_in_pixel().1 as f32;
+    let breaks: Vec<i32> = row_breaks(height, weights)
+        .into_iter()
+        .map(|offset| offset.round() as i32)
+        .collect();
+    area.split_by_breakpoints(Vec::<i32>::new(), breaks)
 }
 pub struct PlottersView<'a, X: AxisType, Y: AxisType> {
     pub model: &'a mut PlottersModel<X, Y>,
@@ -71,8 +170,12 @@ impl<'a, X: AxisType, Y: AxisType> PlottersView<'a, X, Y> {
         window: &mut Window,
         cx: &mut App,
     ) -> Result<(), DrawingAreaErrorKind<plotters_gpui::Error>> {
-        let mut root = GpuiBackend::new(bounds, window, cx).into_drawing_area();
         let base_model = self.model.model.read();
+        if self.model.draw_grid {
+            let cx1 = &mut AxesContext::new(&base_model, window, cx);
+            GridView::new(&base_model.grid).render_axes(cx1);
+        }
+        let mut root = GpuiBackend::new(bounds, window, cx).into_drawing_area();
         let cx1 = &mut AxesContext::new_without_context(&base_model);
         (self.model.chart)(&mut root, cx1);
         root.present()?;
@@ -93,9 +196,19 @@ impl<'a, X: AxisType, Y: AxisType> GeometryPixels for PlottersView<'a, X, Y> {
             bottom: -CONTENT_BOARDER,
             left: -CONTENT_BOARDER,
         });
-        self.model.model.write().update_scale(shrunk_bounds);
+        {
+            let mut model = self.model.model.write();
+            model.update_scale(bounds, shrunk_bounds);
+        }
         if let Err(err) = self.plot(bounds, window, cx) {
             error!("failed to plot: {}", err);
         }
+        if self.model.draw_elements {
+            let mut model = self.model.model.write();
+            let cx1 = &mut AxesContext::new(&model, window, cx);
+            for element in model.elements.iter_mut() {
+                element.render_axes(cx1);
+            }
+        }
     }
 }
@@ -0,0 +1,46 @@
+use gpui::{px, Hsla, Pixels};
+use std::collections::BTreeMap;
+
+/// Points drawn highlighted on top of the elements, by element index and point index
+#[derive(Clone, Debug)]
+pub struct Selection {
+    pub indices: BTreeMap<usize, Vec<usize>>,
+    pub color: Hsla,
+    /// Size of the markers drawn over the selected points
+    pub size: Pixels,
+}
+impl Default for Selection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Selection {
+    pub fn new() -> Self {
+        Self {
+            indices: BTreeMap::new(),
+            color: gpui::red(),
+            size: px(5.0),
+        }
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+    /// Select the point at `index` of the element at `element`
+    pub fn select(&mut self, element: usize, index: usize) {
+        let indices = self.indices.entry(element).or_default();
+        if !indices.contains(&index) {
+            indices.push(index);
+        }
+    }
+    pub fn clear(&mut self) {
+        self.indices.clear();
+    }
+    pub fn is_empty(&self) -> bool {
+        self.indices.values().all(|indices| indices.is_empty())
+    }
+}
@@ -1,9 +1,20 @@
 use crate::figure::axes::model::AxesModel;
-use crate::figure::axes::AxesContext;
+use crate::figure::axes::{AxesContext, FrameStyle, Selection};
 use crate::figure::grid::GridView;
 use crate::figure::ticks::TicksView;
-use crate::geometry::{AxisType, GeometryAxes, GeometryPixels, Line};
-use gpui::{px, App, Bounds, Edges, Pixels, Window};
+use crate::geometry::{
+    measure_text_in, point2, AxisType, GeometryAxes, GeometryPixels, Line, Marker, SeriesStats,
+    Text, TextBackground,
+};
+use crate::utils::math::display_double_smartly;
+use gpui::{point, px, App, Bounds, Edges, PathBuilder, Pixels, Point, Window};
+
+/// Opacity of the elements other than the highlighted one, see `AxesModel::highlight_nearest`
+const HIGHLIGHT_DIM_OPACITY: f32 = 0.25;
+/// Space around and between the rows of `AxesModel::show_stats`
+const STATS_MARGIN: Pixels = px(6.0);
+/// Length of the arrowheads of an open frame, before adding the stroke width
+const ARROWHEAD_SIZE: Pixels = px(8.0);
 
 pub struct AxesView<'a, X: AxisType, Y: AxisType> {
     pub model: &'a mut AxesModel<X, Y>,
@@ -13,16 +24,45 @@ impl<'a, X: AxisType, Y: AxisType> AxesView<'a, X, Y> {
         Self { model }
     }
     pub fn paint(&mut self, window: &mut Window, cx: &mut App, bounds: Bounds<Pixels>) {
+        self.model.full_bounds = bounds;
         {
             let model = &self.model;
             let shrunk_bounds = model.pixel_bounds.into_bounds();
-            for (x, y) in [
-                (shrunk_bounds.origin, shrunk_bounds.top_right()),
-                (shrunk_bounds.top_right(), shrunk_bounds.bottom_right()),
-                (shrunk_bounds.bottom_right(), shrunk_bounds.bottom_left()),
-                (shrunk_bounds.bottom_left(), shrunk_bounds.origin),
+            let style = model.frame_style;
+            for (visible, x, y) in [
+                (
+                    model.spines.top,
+                    shrunk_bounds.origin,
+                    shrunk_bounds.top_right(),
+                ),
+                (
+                    model.spines.right,
+                    shrunk_bounds.top_right(),
+                    shrunk_bounds.bottom_right(),
+                ),
+                (
+                    model.spines.bottom,
+                    shrunk_bounds.bottom_right(),
+                    shrunk_bounds.bottom_left(),
+                ),
+                (
+                    model.spines.left,
+                    shrunk_bounds.bottom_left(),
+                    shrunk_bounds.origin,
+                ),
             ] {
-                Line::between_points(x.into(), y.into()).render(window, cx, Some(shrunk_bounds));
+                if !visible {
+                    continue;
+                }
+                paint_frame_segment(window, cx, x, y, style);
+            }
+            if style.arrows {
+                if model.spines.bottom {
+                    paint_arrowhead(window, shrunk_bounds.bottom_right(), point(1.0, 0.0), style);
+                }
+                if model.spines.left {
+                    paint_arrowhead(window, shrunk_bounds.origin, point(0.0, -1.0), style);
+                }
             }
         }
 
@@ -30,31 +70,213 @@ impl<'a, X: AxisType, Y: AxisType> AxesView<'a, X, Y> {
 
         let mut ticks = TicksView::new(self.model);
         {
+            let full_bounds = cx1.full_bounds;
             let (window, cx1) = cx1.cx.as_mut().unwrap();
-            ticks.render(window, cx1, bounds);
+            ticks.render(window, cx1, full_bounds);
         }
         let mut grid = GridView::new(&self.model.grid);
         {
             grid.render_axes(cx1);
         }
 
-        for element in self.model.elements.iter_mut() {
+        if self.model.draw_origin_axes {
+            self.paint_origin_axes(cx1);
+        }
+
+        let model = &mut *self.model;
+        model.painted_bounds.clear();
+        let highlighted = model.highlighted;
+        for (index, element) in model.elements.iter_mut().enumerate() {
+            cx1.painted_bounds = None;
+            let dimmed = highlighted.is_some_and(|highlighted| highlighted != index);
+            cx1.opacity = if dimmed { HIGHLIGHT_DIM_OPACITY } else { 1.0 };
             element.render_axes(cx1);
+            model
+                .painted_bounds
+                .push(cx1.painted_bounds.unwrap_or(cx1.data_clip));
+        }
+        cx1.opacity = 1.0;
+
+        if let Some(selection) = &self.model.selection {
+            self.paint_selection(selection, cx1);
+        }
+
+        if self.model.show_data_cursor {
+            if let Some(position) = self.model.cursor_position {
+                self.paint_data_cursor(position, cx1);
+            }
+        }
+
+        if self.model.show_stats {
+            let stats = self.model.visible_stats();
+            self.paint_stats(&stats, cx1);
+        }
+    }
+    /// Draw the lines through zero that lie within the visible range, across the plotting area
+    fn paint_origin_axes(&self, cx: &mut AxesContext<X, Y>) {
+        let model = &self.model;
+        let area = model.pixel_bounds.into_bounds();
+        let style = model.origin_axes_style;
+        let zero = point2(X::from_f64(0.0), Y::from_f64(0.0));
+        let origin = cx.transform_point(zero);
+        let (window, app) = cx.cx.as_mut().unwrap();
+        if model.axes_bounds.x.contains(zero.x) {
+            let top = point(origin.x, area.top());
+            let bottom = point(origin.x, area.bottom());
+            paint_frame_segment(window, app, bottom, top, style);
+        }
+        if model.axes_bounds.y.contains(zero.y) {
+            let left = point(area.left(), origin.y);
+            let right = point(area.right(), origin.y);
+            paint_frame_segment(window, app, left, right, style);
+        }
+    }
+    /// Draw the selected points over their elements
+    fn paint_selection(&self, selection: &Selection, cx: &mut AxesContext<X, Y>) {
+        for (element, indices) in selection.indices.iter() {
+            let Some(element) = self.model.elements.get(*element) else {
+                continue;
+            };
+            for index in indices.iter().cloned() {
+                let Some(position) = element.point_at(index) else {
+                    continue;
+                };
+                Marker::new(position, selection.size)
+                    .color(selection.color)
+                    .render_axes(cx);
+            }
+        }
+    }
+    /// Mark and label the value of every element at the hovered x
+    fn paint_data_cursor(&self, position: Point<Pixels>, cx: &mut AxesContext<X, Y>) {
+        let model = &self.model;
+        if !model.pixel_bounds.into_bounds().contains(&position) {
+            return;
+        }
+        let x = model
+            .axes_bounds
+            .x
+            .transform_reverse(model.pixel_bounds.x, position.x);
+        for element in model.elements.iter() {
+            let Some(y) = element.value_at_x(x) else {
+                continue;
+            };
+            let value = point2(x, y);
+            Marker::new(value, px(4.0)).render_axes(cx);
+
+            let origin = cx.transform_point(value) + point(px(6.0), px(-18.0));
+            let mut text = Text::new(
+                origin.into(),
+                model.label_font_size,
+                format!("{}, {}", x.format(), y.format()),
+            )
+            .background(TextBackground::new(gpui::white()).border(px(1.0), gpui::black()));
+            text.font_family = model.font_family.clone();
+            let (window, app) = cx.cx.as_mut().unwrap();
+            text.render(window, app, None);
+        }
+    }
+    /// List the stats of each series, one row per series, in the top right corner of the plotting area
+    fn paint_stats(&self, stats: &[(Option<String>, SeriesStats<Y>)], cx: &mut AxesContext<X, Y>) {
+        let size = self.model.label_font_size;
+        let family = self.model.font_family.as_ref();
+        let area = self.model.pixel_bounds.into_bounds();
+        let (window, app) = cx.cx.as_mut().unwrap();
+        let mut top = area.top() + STATS_MARGIN;
+        for (label, stats) in stats.iter() {
+            let mut row = format!(
+                "n={} min={} max={} mean={} last={}",
+                stats.count,
+                stats.min.format(),
+                stats.max.format(),
+                display_double_smartly(stats.mean),
+                stats.last.format()
+            );
+            if let Some(label) = label {
+                row = format!("{}: {}", label, row);
+            }
+            let width = measure_text_in(window, &row, size, family).width;
+            let origin = point2(area.right() - STATS_MARGIN - width, top);
+            let mut text = Text::new(origin, size, row)
+                .background(TextBackground::new(gpui::white()).border(px(1.0), gpui::black()));
+            text.font_family = family.cloned();
+            text.render(window, app, None);
+            top += size + STATS_MARGIN;
         }
     }
 }
 
-const CONTENT_BOARDER: Pixels = px(30.0);
+/// Stroke the border from `from` to `to`, dashed if the style asks for it
+fn paint_frame_segment(
+    window: &mut Window,
+    cx: &mut App,
+    from: Point<Pixels>,
+    to: Point<Pixels>,
+    style: FrameStyle,
+) {
+    let stroke = |window: &mut Window, cx: &mut App, a: Point<Pixels>, b: Point<Pixels>| {
+        Line::between_points(a.into(), b.into())
+            .width(style.width)
+            .color(style.color)
+            .render(window, cx, None);
+    };
+    let Some((dash, gap)) = style.dash else {
+        stroke(window, cx, from, to);
+        return;
+    };
+    let delta = to - from;
+    let length = (delta.x.0 * delta.x.0 + delta.y.0 * delta.y.0).sqrt();
+    let period = dash.0 + gap.0;
+    if length == 0.0 || period <= 0.0 {
+        stroke(window, cx, from, to);
+        return;
+    }
+    let at = |t: f32| {
+        point(
+            from.x + delta.x * (t / length),
+            from.y + delta.y * (t / length),
+        )
+    };
+    let mut t = 0.0;
+    while t < length {
+        stroke(window, cx, at(t), at((t + dash.0).min(length)));
+        t += period;
+    }
+}
+/// A filled arrowhead with its tip at `tip`, pointing along the unit vector `direction`
+fn paint_arrowhead(
+    window: &mut Window,
+    tip: Point<Pixels>,
+    direction: Point<f32>,
+    style: FrameStyle,
+) {
+    let length = ARROWHEAD_SIZE.0 + style.width.0 * 2.0;
+    let half_width = length / 2.5;
+    let base = point(
+        tip.x - px(direction.x * length),
+        tip.y - px(direction.y * length),
+    );
+    let normal = point(-direction.y * half_width, direction.x * half_width);
+    let mut builder = PathBuilder::fill();
+    builder.move_to(tip);
+    builder.line_to(point(base.x + px(normal.x), base.y + px(normal.y)));
+    builder.line_to(point(base.x - px(normal.x), base.y - px(normal.y)));
+    builder.close();
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, style.color);
+    }
+}
 
 impl<'a, X: AxisType, Y: AxisType> GeometryPixels for AxesView<'a, X, Y> {
     fn render_pixels(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        let margins = self.model.margins(window);
         let shrunk_bounds = bounds.extend(Edges {
-            top: px(-0.0),
-            right: -CONTENT_BOARDER,
-            bottom: -CONTENT_BOARDER,
-            left: -CONTENT_BOARDER,
+            top: -margins.top,
+            right: -margins.right,
+            bottom: -margins.bottom,
+            left: -margins.left,
         });
-        self.model.update_scale(shrunk_bounds);
+        self.model.update_scale(bounds, shrunk_bounds);
         self.paint(window, cx, bounds);
     }
 }
@@ -1,20 +1,92 @@
 use crate::figure::plot::{PlotModel, PlotView};
 use crate::figure::text::centered_text;
+use crate::geometry::{measure_text, point2, FontConfig, Series, Text};
 use gpui::{
-    div, App, AppContext, Context, Entity, IntoElement, ParentElement, Render, Styled, Window,
+    canvas, div, point, px, App, AppContext, Context, Edges, Entity, Hsla, IntoElement,
+    ParentElement, Pixels, Render, Styled, Window,
 };
 use parking_lot::RwLock;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+/// Which corner of the figure a `Watermark` sits in
+#[derive(Clone, Copy, Debug, Default)]
+pub enum WatermarkCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Faint text drawn over a figure, e.g. for branded exports
+#[derive(Clone, Debug)]
+pub struct Watermark {
+    pub text: String,
+    pub corner: WatermarkCorner,
+    pub size: Pixels,
+    pub color: Hsla,
+    /// Distance from the edges of the figure
+    pub margin: Pixels,
+}
+impl Watermark {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            corner: WatermarkCorner::default(),
+            size: px(24.0),
+            color: gpui::black().opacity(0.15),
+            margin: px(8.0),
+        }
+    }
+    pub fn corner(mut self, corner: WatermarkCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = color;
+        self
+    }
+    fn paint(&self, bounds: gpui::Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        let width = measure_text(window, &self.text, self.size).width;
+        let left = bounds.left() + self.margin;
+        let right = bounds.right() - self.margin - width;
+        let top = bounds.top() + self.margin;
+        let bottom = bounds.bottom() - self.margin - self.size;
+        let origin = match self.corner {
+            WatermarkCorner::TopLeft => point(left, top),
+            WatermarkCorner::TopRight => point(right, top),
+            WatermarkCorner::BottomLeft => point(left, bottom),
+            WatermarkCorner::BottomRight => point(right, bottom),
+        };
+        Text::new(point2(origin.x, origin.y), self.size, self.text.clone())
+            .color(self.color)
+            .render(window, cx, None);
+    }
+}
+
 pub struct FigureModel {
     pub title: String,
+    pub watermark: Option<Watermark>,
+    /// Give every axes the widest left margin among them, so stacked plots line up.
+    /// Measured each time the figure renders
+    pub align_left_margins: bool,
+    /// Typography of the title and every axes. Applied each time the figure renders,
+    /// `None` leaves the axes as they are
+    pub font: Option<FontConfig>,
     pub plots: Vec<Arc<RwLock<PlotModel>>>,
 }
 impl Debug for FigureModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FigureContext")
             .field("title", &self.title)
+            .field("watermark", &self.watermark)
+            .field("align_left_margins", &self.align_left_margins)
+            .field("font", &self.font)
             .field("plots", &self.plots)
             .finish()
     }
@@ -24,6 +96,9 @@ impl FigureModel {
     pub fn new(title: String) -> Self {
         Self {
             title,
+            watermark: None,
+            align_left_margins: false,
+            font: None,
             plots: Vec::new(),
         }
     }
@@ -43,6 +118,43 @@ impl FigureModel {
 
         plot_fn(&mut model.write());
     }
+    /// Call `f` with every series in the figure, e.g. to build a legend or apply a theme
+    pub fn for_each_series(&mut self, mut f: impl FnMut(&mut dyn Series)) {
+        for plot in self.plots.iter() {
+            plot.write().for_each_series(&mut f);
+        }
+    }
+    /// Apply `font` to every axes in the figure
+    pub fn apply_font(&mut self, font: &FontConfig) {
+        for plot in self.plots.iter() {
+            for axes in plot.read().axes.iter() {
+                axes.write().set_font(font);
+            }
+        }
+    }
+    /// Measure the left margin every axes needs, then apply the widest to all of them.
+    /// The other minimum margins of each axes stay as they are
+    pub fn update_left_margins(&mut self, window: &Window) {
+        let mut left = px(0.0);
+        for plot in self.plots.iter() {
+            for axes in plot.read().axes.iter() {
+                let mut axes = axes.write();
+                let existing = axes.min_margins();
+                axes.set_min_margins(Edges {
+                    left: px(0.0),
+                    ..existing
+                });
+                left = px(left.0.max(axes.margins(window).left.0));
+            }
+        }
+        for plot in self.plots.iter() {
+            for axes in plot.read().axes.iter() {
+                let mut axes = axes.write();
+                let existing = axes.min_margins();
+                axes.set_min_margins(Edges { left, ..existing });
+            }
+        }
+    }
     /// Update the figure model.
     pub fn update(&mut self) {
         for plot in self.plots.iter() {
@@ -52,20 +164,37 @@ impl FigureModel {
     }
 }
 
+/// How much vertical space a `FigureView` takes
+#[derive(Clone, Copy, Debug, Default)]
+pub enum FigureHeight {
+    /// Fill the parent, sharing it between the plots
+    #[default]
+    Fill,
+    /// Give every plot this height and size the figure to fit them,
+    /// e.g. to stack several figures in a scrollable page
+    FitContent(Pixels),
+}
+
 /// A Figure is per definition of matplotlib: https://matplotlib.org/stable/users/explain/quick_start.html
 /// It contains a title, a canvas, 2 axes, and a legend.
 /// The canvas is the main area where the plot is drawn.
 pub struct FigureView {
     pub model: Arc<RwLock<FigureModel>>,
     pub plots: Vec<Entity<PlotView>>,
+    pub height: FigureHeight,
 }
 impl FigureView {
     pub fn new(model: Arc<RwLock<FigureModel>>) -> Self {
         Self {
             model,
             plots: Vec::new(),
+            height: FigureHeight::Fill,
         }
     }
+    pub fn height(mut self, height: FigureHeight) -> Self {
+        self.height = height;
+        self
+    }
     fn add_views(&mut self, cx: &mut App) {
         for i in self.plots.len()..self.model.read().plots.len() {
             let plot_model = self.model.read().plots[i].clone();
@@ -76,15 +205,52 @@ impl FigureView {
     }
 }
 impl Render for FigureView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.add_views(cx);
-        div()
+        {
+            let mut model = self.model.write();
+            // before measuring the margins, which depend on the tick font
+            if let Some(font) = model.font.clone() {
+                model.apply_font(&font);
+            }
+            if model.align_left_margins {
+                model.update_left_margins(window);
+            }
+        }
+        // painted on a canvas without a hitbox, so the plots below still get the mouse events
+        let watermark = self.model.read().watermark.clone().map(|watermark| {
+            canvas(
+                |_, _, _| (),
+                move |bounds, _, window, cx| watermark.paint(bounds, window, cx),
+            )
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+        });
+        let mut title = div().child(centered_text(self.model.read().title.clone()));
+        if let Some(font) = self.model.read().font.as_ref() {
+            title = title.text_size(font.title_size);
+            if let Some(family) = font.family.clone() {
+                title = title.font_family(family);
+            }
+        }
+        let root = div()
+            .relative()
             .flex()
             .flex_col()
-            .size_full()
+            .w_full()
             .bg(gpui::white())
             .text_color(gpui::black())
-            .child(centered_text(self.model.read().title.clone()))
-            .children(self.plots.clone())
+            .child(title);
+        let root = match self.height {
+            FigureHeight::Fill => root.h_full().children(self.plots.clone()),
+            FigureHeight::FitContent(plot_height) => root.children(
+                self.plots
+                    .iter()
+                    .map(|plot| div().w_full().h(plot_height).child(plot.clone())),
+            ),
+        };
+        root.children(watermark)
     }
 }
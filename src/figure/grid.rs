@@ -1,17 +1,39 @@
 use crate::figure::axes::AxesContext;
-use crate::geometry::{point2, size2, AxisType, GeometryAxes, Line, Size2};
-use gpui::{size, Size};
+use crate::geometry::{point2, size2, AxisRange, AxisType, GeometryAxes, Line, Size2, Text};
+use crate::utils::math::nice_step;
+use gpui::{px, size, Hsla, Pixels, Size};
+
+/// Font size of the labels of custom grid lines
+const CUSTOM_LINE_LABEL_SIZE: Pixels = px(10.0);
 
 pub enum GridType<X: AxisType, Y: AxisType> {
     Density(Size2<X::Delta, Y::Delta>),
     Numbers(usize, usize),
+    /// About this many lines per axis, at multiples of a nice step, so the values stay put
+    /// while panning. May give one more or less than asked
+    AdaptiveNumbers(usize, usize),
+}
+
+/// A grid line at a fixed value, e.g. a session boundary, drawn besides the regular grid
+#[derive(Clone, Debug)]
+pub struct CustomGridLine<T> {
+    pub value: T,
+    pub color: Hsla,
+    pub label: Option<String>,
 }
 
 pub struct GridModel<X: AxisType, Y: AxisType> {
     pub ty: GridType<X, Y>,
     pub movable: bool,
+    /// Recompute the lines on every pan step. When off, they are recomputed once the pan ends,
+    /// which keeps fast drags over dense grids smooth
+    pub regenerate_while_panning: bool,
     pub grid_x_lines: Vec<X>,
     pub grid_y_lines: Vec<Y>,
+    /// Vertical lines kept across updates of the regular grid
+    pub custom_x_lines: Vec<CustomGridLine<X>>,
+    /// Horizontal lines kept across updates of the regular grid
+    pub custom_y_lines: Vec<CustomGridLine<Y>>,
 }
 impl<X: AxisType, Y: AxisType> GridModel<X, Y> {
     pub fn from_density(x: X::Delta, y: Y::Delta) -> Self {
@@ -20,18 +42,48 @@ impl<X: AxisType, Y: AxisType> GridModel<X, Y> {
     pub fn from_numbers(x: usize, y: usize) -> Self {
         Self::new(GridType::Numbers(x, y))
     }
+    pub fn from_adaptive_numbers(x: usize, y: usize) -> Self {
+        Self::new(GridType::AdaptiveNumbers(x, y))
+    }
     pub fn new(ty: GridType<X, Y>) -> Self {
         Self {
             ty,
             movable: true,
+            regenerate_while_panning: true,
             grid_x_lines: Vec::new(),
             grid_y_lines: Vec::new(),
+            custom_x_lines: Vec::new(),
+            custom_y_lines: Vec::new(),
         }
     }
     pub fn with_fixed(mut self) -> Self {
         self.movable = false;
         self
     }
+    pub fn regenerate_while_panning(mut self, regenerate: bool) -> Self {
+        self.regenerate_while_panning = regenerate;
+        self
+    }
+    /// Add a vertical line at `x`, labeled at the top of the plotting area
+    pub fn add_custom_x_line(&mut self, x: X, color: Hsla, label: Option<String>) {
+        self.custom_x_lines.push(CustomGridLine {
+            value: x,
+            color,
+            label,
+        });
+    }
+    /// Add a horizontal line at `y`, labeled at the left of the plotting area
+    pub fn add_custom_y_line(&mut self, y: Y, color: Hsla, label: Option<String>) {
+        self.custom_y_lines.push(CustomGridLine {
+            value: y,
+            color,
+            label,
+        });
+    }
+    pub fn clear_custom_lines(&mut self) {
+        self.custom_x_lines.clear();
+        self.custom_y_lines.clear();
+    }
     fn should_update_grid(&self, _axes_bounds: &AxesContext<X, Y>) -> bool {
         if self.grid_x_lines.is_empty() || self.grid_y_lines.is_empty() {
             return true;
@@ -45,32 +97,56 @@ impl<X: AxisType, Y: AxisType> GridModel<X, Y> {
         self.update_grid(axes_bounds);
     }
     pub fn update_grid(&mut self, axes_bounds: &AxesContext<X, Y>) {
-        let density = match self.ty {
-            GridType::Density(density) => density.to_f64(),
-            GridType::Numbers(x, y) => size(
-                axes_bounds.axes_bounds.x.size_in_f64() / x as f64,
-                axes_bounds.axes_bounds.y.size_in_f64() / y as f64,
-            ),
-        };
-        self.update_grid_by_density(axes_bounds, density);
+        let (x, y) = (axes_bounds.axes_bounds.x, axes_bounds.axes_bounds.y);
+        match self.ty {
+            GridType::Density(density) => self.update_grid_by_density(x, y, density.to_f64()),
+            GridType::Numbers(x_count, y_count) => {
+                let density = size(
+                    x.size_in_f64() / x_count as f64,
+                    y.size_in_f64() / y_count as f64,
+                );
+                self.update_grid_by_density(x, y, density)
+            }
+            GridType::AdaptiveNumbers(x_count, y_count) => {
+                let x_step = nice_step(x.size_in_f64().abs() / x_count as f64);
+                let y_step = nice_step(y.size_in_f64().abs() / y_count as f64);
+                self.update_grid_by_density(
+                    aligned_range(x, x_step),
+                    aligned_range(y, y_step),
+                    size(x_step, y_step),
+                )
+            }
+        }
     }
-    fn update_grid_by_density(&mut self, axes_bounds: &AxesContext<X, Y>, density: Size<f64>) {
+    fn update_grid_by_density(&mut self, x: AxisRange<X>, y: AxisRange<Y>, density: Size<f64>) {
+        // a zero or non-finite step would never reach the end of the range
+        let valid = |step: f64| step.is_finite() && step.abs() > 0.0;
+        if !valid(density.width) || !valid(density.height) {
+            self.grid_x_lines.clear();
+            self.grid_y_lines.clear();
+            return;
+        }
         // TODO: clap beforehand to have better performance
-        self.grid_x_lines = axes_bounds
-            .axes_bounds
-            .x
-            .iter_step_by_f64(density.width)
-            .collect();
-        self.grid_x_lines
-            .retain(|x| axes_bounds.axes_bounds.x.contains(*x));
-        self.grid_y_lines = axes_bounds
-            .axes_bounds
-            .y
-            .iter_step_by_f64(density.height)
-            .collect();
-        self.grid_y_lines
-            .retain(|y| axes_bounds.axes_bounds.y.contains(*y));
+        self.grid_x_lines = x.iter_step_by_f64(density.width).collect();
+        self.grid_x_lines.retain(|value| x.contains(*value));
+        self.grid_y_lines = y.iter_step_by_f64(density.height).collect();
+        self.grid_y_lines.retain(|value| y.contains(*value));
+    }
+}
+
+/// `range` with its start moved forward onto the first multiple of `step`
+fn aligned_range<T: AxisType>(range: AxisRange<T>, step: f64) -> AxisRange<T> {
+    if !step.is_finite() || step <= 0.0 {
+        return range;
     }
+    let base = range.base.to_f64();
+    let start = base + range.min_to_base;
+    let aligned = if range.is_descending() {
+        (start / step).floor() * step
+    } else {
+        (start / step).ceil() * step
+    };
+    AxisRange::new_with_base_f64(range.base, aligned - base, range.max_to_base)
 }
 
 pub struct GridView<'a, X: AxisType, Y: AxisType> {
@@ -84,20 +160,70 @@ impl<'a, X: AxisType, Y: AxisType> GridView<'a, X, Y> {
 impl<'a, X: AxisType, Y: AxisType> GeometryAxes for GridView<'a, X, Y> {
     type X = X;
     type Y = Y;
+    /// Lines span exactly the plotting area, `cx.data_clip`, and never reach into the margins
     fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
         let grid = self.model;
+        let area = cx.data_clip;
+        let mut lines = Vec::new();
         for x in grid.grid_x_lines.iter().cloned() {
-            let top_point = point2(x, cx.axes_bounds.y.min());
-            let bottom_point = point2(x, cx.axes_bounds.y.max());
-            let mut line = Line::between_points(top_point, bottom_point);
-            line.render_axes(cx);
+            let x = cx.axes_bounds.x.transform(cx.pixel_bounds.x, x);
+            if x < area.left() || x > area.right() {
+                continue;
+            }
+            lines.push(Line::between_points(
+                point2(x, area.top()),
+                point2(x, area.bottom()),
+            ));
         }
-
         for y in grid.grid_y_lines.iter().cloned() {
-            let left_point = point2(cx.axes_bounds.x.min(), y);
-            let right_point = point2(cx.axes_bounds.x.max(), y);
-            let mut line = Line::between_points(left_point, right_point);
-            line.render_axes(cx);
+            let y = cx.axes_bounds.y.transform(cx.pixel_bounds.y, y);
+            if y < area.top() || y > area.bottom() {
+                continue;
+            }
+            lines.push(Line::between_points(
+                point2(area.left(), y),
+                point2(area.right(), y),
+            ));
+        }
+        let mut labels = Vec::new();
+        for custom in grid.custom_x_lines.iter() {
+            let x = cx.axes_bounds.x.transform(cx.pixel_bounds.x, custom.value);
+            if x < area.left() || x > area.right() {
+                continue;
+            }
+            lines.push(
+                Line::between_points(point2(x, area.top()), point2(x, area.bottom()))
+                    .color(custom.color),
+            );
+            if let Some(label) = &custom.label {
+                labels.push((point2(x + px(2.0), area.top()), custom.color, label));
+            }
+        }
+        for custom in grid.custom_y_lines.iter() {
+            let y = cx.axes_bounds.y.transform(cx.pixel_bounds.y, custom.value);
+            if y < area.top() || y > area.bottom() {
+                continue;
+            }
+            lines.push(
+                Line::between_points(point2(area.left(), y), point2(area.right(), y))
+                    .color(custom.color),
+            );
+            if let Some(label) = &custom.label {
+                let origin = point2(area.left() + px(2.0), y - CUSTOM_LINE_LABEL_SIZE - px(2.0));
+                labels.push((origin, custom.color, label));
+            }
+        }
+        let Some((window, app)) = cx.cx.as_mut() else {
+            return;
+        };
+        for mut line in lines {
+            // the endpoints already lie on the edges of the area, which `contains` may exclude
+            line.render(window, app, None);
+        }
+        for (origin, color, label) in labels {
+            Text::new(origin, CUSTOM_LINE_LABEL_SIZE, label.clone())
+                .color(color)
+                .render(window, app, None);
         }
     }
 }
@@ -1,29 +1,93 @@
-use crate::figure::axes::{Axes, AxesContext, AxesModel, PlottersModel};
+use crate::figure::axes::{Axes, AxesContext, AxesModel, PlottersModel, RenderBackend};
 use crate::figure::SharedModel;
 use crate::fps::FpsModel;
-use crate::geometry::AxisType;
+use crate::geometry::{AxesBounds, AxisType, Series};
 use gpui::{
-    canvas, div, Bounds, Context, InteractiveElement, IntoElement, MouseButton, MouseDownEvent,
-    MouseMoveEvent, ParentElement, Pixels, Point, Render, ScrollDelta, ScrollWheelEvent, Styled,
-    Window,
+    canvas, div, fill, point, px, Bounds, Context, Edges, InteractiveElement, IntoElement,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point,
+    Render, ScrollDelta, ScrollWheelEvent, StatefulInteractiveElement, Styled, Timer, Window,
 };
 use parking_lot::RwLock;
 use plotters::coord::Shift;
 use plotters::drawing::DrawingArea;
 use plotters_gpui::backend::GpuiBackend;
+use std::any::Any;
 use std::fmt::Debug;
+use std::ops::Range;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// A box zoom only starts once the drag spans this much in both directions
+const BOX_ZOOM_MIN_DRAG: Pixels = px(4.0);
+/// A press that moves less than this before release counts as a click rather than a pan
+const CLICK_MAX_DRAG: Pixels = px(4.0);
+
+/// An element on one of a plot's axes, by index
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EditTarget {
+    pub axes: usize,
+    pub element: usize,
+}
+
+/// Axes folded into `target` by `PlotModel::merge_axes`, kept so they can be split again
+struct MergedAxes {
+    target: SharedModel<dyn Axes>,
+    /// Each source axes with the range of the target's elements that came from it
+    sources: Vec<(SharedModel<dyn Axes>, Range<usize>)>,
+}
+
+/// The point a rubberband zoom scales around
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RubberbandAnchor {
+    /// Where the drag started
+    #[default]
+    DragStart,
+    /// The center of the plot
+    Center,
+    /// A fixed point, relative to the top left corner of the plot
+    Fixed(Point<Pixels>),
+}
+
+type LeaveFn = Box<dyn FnMut() + Send>;
+
 pub struct PlotModel {
     pub panning: bool,
     pub zooming: bool,
     pub zoom_swipe_precision: f64,
     pub zoom_scroll_precision: f64,
     pub zoom_rubberband_precision: f64,
+    /// How long scrolling must pause before a scroll zoom gesture ends. Scroll steps within a
+    /// gesture accumulate into one zoom from where it began, the next step after the pause
+    /// starts a new gesture around the cursor
+    pub zoom_idle_timeout: Duration,
+    /// What right-drag and Alt+left-drag zooms scale around
+    pub rubberband_anchor: RubberbandAnchor,
+    /// Alt+left-drag zooms each axis by how far the drag went along it, x drags for x and
+    /// y drags for y. Also covers platforms that don't deliver right-drags
+    pub alt_drag_zoom: bool,
+    /// Reverse the zoom direction of trackpad swipes
+    pub invert_swipe_zoom: bool,
+    /// Reverse the zoom direction of mouse wheel scrolls
+    pub invert_scroll_zoom: bool,
+    /// Redraw continuously, e.g. for plots driven by time
+    pub animated: bool,
+    /// Suspend the continuous redraw of an animated plot
+    pub paused: bool,
+    /// Throttle the continuous redraw of an animated plot to this rate
+    pub max_fps: Option<f32>,
+    /// Round the corners of the plot, keeping the content inside them
+    pub corner_radius: Pixels,
+    /// Left-drag brushes an x range, reported through `AxesModel::on_brush`, instead of panning
+    pub brush_mode: bool,
+    /// Start and current pixel x of the brush, kept after the drag ends
+    pub brush: Option<(Pixels, Pixels)>,
+    /// Clicking the plot appends a point to this element, e.g. a `Line` being drawn
+    pub edit_target: Option<EditTarget>,
     pub fps: FpsModel,
     pub bounds: Bounds<Pixels>,
     pub axes: Vec<SharedModel<dyn Axes>>,
+    merged: Vec<MergedAxes>,
+    on_leave: Option<LeaveFn>,
 }
 impl Debug for PlotModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -33,8 +97,22 @@ impl Debug for PlotModel {
             .field("zoom_swipe_precision", &self.zoom_swipe_precision)
             .field("zoom_scroll_precision", &self.zoom_scroll_precision)
             .field("zoom_rubberband_precision", &self.zoom_rubberband_precision)
+            .field("zoom_idle_timeout", &self.zoom_idle_timeout)
+            .field("rubberband_anchor", &self.rubberband_anchor)
+            .field("alt_drag_zoom", &self.alt_drag_zoom)
+            .field("invert_swipe_zoom", &self.invert_swipe_zoom)
+            .field("invert_scroll_zoom", &self.invert_scroll_zoom)
+            .field("animated", &self.animated)
+            .field("paused", &self.paused)
+            .field("max_fps", &self.max_fps)
+            .field("corner_radius", &self.corner_radius)
+            .field("brush_mode", &self.brush_mode)
+            .field("brush", &self.brush)
+            .field("edit_target", &self.edit_target)
             .field("bounds", &self.bounds)
             .field("axes", &self.axes.len())
+            .field("merged", &self.merged.len())
+            .field("on_leave", &self.on_leave.is_some())
             .finish()
     }
 }
@@ -51,9 +129,23 @@ impl PlotModel {
             zoom_swipe_precision: 1.0 / 200.0,
             zoom_scroll_precision: 1.0 / 100.0,
             zoom_rubberband_precision: 1.0 / 400.0,
+            zoom_idle_timeout: Duration::from_secs_f32(0.2),
+            rubberband_anchor: RubberbandAnchor::default(),
+            alt_drag_zoom: true,
+            invert_swipe_zoom: false,
+            invert_scroll_zoom: false,
+            animated: false,
+            paused: false,
+            max_fps: None,
+            corner_radius: px(0.0),
+            brush_mode: false,
+            brush: None,
+            edit_target: None,
             fps: FpsModel::new(),
             bounds: Bounds::default(),
             axes: Vec::new(),
+            merged: Vec::new(),
+            on_leave: None,
         }
     }
     pub fn clear_axes(&mut self) {
@@ -76,6 +168,34 @@ impl PlotModel {
         self.axes.push(model as SharedModel<dyn Axes>);
     }
 
+    /// Plot `axes` with the native renderer, returning a handle to keep updating it
+    pub fn plot<X: AxisType, Y: AxisType>(
+        &mut self,
+        axes: AxesModel<X, Y>,
+    ) -> SharedModel<AxesModel<X, Y>> {
+        #[allow(clippy::arc_with_non_send_sync)]
+        let model = Arc::new(RwLock::new(axes));
+        self.add_axes(model)
+    }
+    /// Plot `axes` through the plotters chart `draw`, with the native elements of `axes`
+    /// drawn on top in the same entry. Returns a handle to keep updating it
+    #[cfg(feature = "plotters")]
+    pub fn plot_plotters<X: AxisType, Y: AxisType>(
+        &mut self,
+        axes: AxesModel<X, Y>,
+        draw: impl FnMut(&mut DrawingArea<GpuiBackend, Shift>, &mut AxesContext<X, Y>) + 'static,
+    ) -> SharedModel<AxesModel<X, Y>> {
+        #[allow(clippy::arc_with_non_send_sync)]
+        let model = Arc::new(RwLock::new(axes));
+        let plotters = PlottersModel::new(model.clone(), Box::new(draw)).draw_elements(true);
+        self.add_plotters(plotters);
+        model
+    }
+    /// Which renderer draws each axes, in order
+    pub fn backends(&self) -> Vec<RenderBackend> {
+        self.axes.iter().map(|axes| axes.read().backend()).collect()
+    }
+
     #[cfg(feature = "plotters")]
     pub fn add_axes_plotters<X: AxisType, Y: AxisType>(
         &mut self,
@@ -84,6 +204,11 @@ impl PlotModel {
     ) {
         let axes = PlottersModel::new(model, Box::new(draw));
 
+        self.add_plotters(axes);
+    }
+    /// Add a configured plotters chart, e.g. one that draws the native grid behind it
+    #[cfg(feature = "plotters")]
+    pub fn add_plotters<X: AxisType, Y: AxisType>(&mut self, axes: PlottersModel<X, Y>) {
         self.axes.push(Arc::new(RwLock::new(axes)));
     }
     pub fn update(&mut self) {
@@ -91,6 +216,92 @@ impl PlotModel {
             axes.write().update();
         }
     }
+    /// Move the elements of the axes at `indices` into the first of them, overlaying them on
+    /// shared axes. All of them must be `AxesModel<X, Y>`, otherwise nothing changes.
+    /// Returns whether the axes were merged
+    pub fn merge_axes<X: AxisType, Y: AxisType>(&mut self, indices: &[usize]) -> bool {
+        let Some((&target_index, source_indices)) = indices.split_first() else {
+            return false;
+        };
+        let mut seen = Vec::with_capacity(indices.len());
+        for &index in indices {
+            let Some(axes) = self.axes.get(index) else {
+                return false;
+            };
+            if seen.iter().any(|other| Arc::ptr_eq(other, axes))
+                || downcast_axes::<X, Y>(&mut *axes.write()).is_none()
+            {
+                return false;
+            }
+            seen.push(axes.clone());
+        }
+        if source_indices.is_empty() {
+            return false;
+        }
+        let target = self.axes[target_index].clone();
+        let mut sources = Vec::with_capacity(source_indices.len());
+        {
+            let mut target_guard = target.write();
+            let target_model = downcast_axes::<X, Y>(&mut *target_guard).unwrap();
+            for &index in source_indices {
+                let source = self.axes[index].clone();
+                let mut source_guard = source.write();
+                let source_model = downcast_axes::<X, Y>(&mut *source_guard).unwrap();
+                let range = target_model.append_elements(source_model);
+                drop(source_guard);
+                sources.push((source, range));
+            }
+            target_model.update();
+        }
+        let mut removed = source_indices.to_vec();
+        removed.sort_unstable();
+        for index in removed.into_iter().rev() {
+            self.axes.remove(index);
+        }
+        match self
+            .merged
+            .iter_mut()
+            .find(|merged| Arc::ptr_eq(&merged.target, &target))
+        {
+            Some(merged) => merged.sources.extend(sources),
+            None => self.merged.push(MergedAxes { target, sources }),
+        }
+        true
+    }
+    /// Undo `merge_axes` for the axes at `index`, moving the merged elements back to their
+    /// own axes, which are reinserted right after it. Returns whether anything was split
+    pub fn split_axes<X: AxisType, Y: AxisType>(&mut self, index: usize) -> bool {
+        let Some(target) = self.axes.get(index).cloned() else {
+            return false;
+        };
+        let Some(position) = self
+            .merged
+            .iter()
+            .position(|merged| Arc::ptr_eq(&merged.target, &target))
+        else {
+            return false;
+        };
+        let mut target_guard = target.write();
+        let Some(target_model) = downcast_axes::<X, Y>(&mut *target_guard) else {
+            return false;
+        };
+        let merged = self.merged.remove(position);
+        // take the ranges back from the end so the earlier ones stay valid
+        for (source, range) in merged.sources.iter().rev() {
+            let elements = target_model.drain_elements(range.clone());
+            let mut source_guard = source.write();
+            if let Some(source_model) = downcast_axes::<X, Y>(&mut *source_guard) {
+                source_model.prepend_elements(elements);
+                source_model.update();
+            }
+        }
+        target_model.update();
+        drop(target_guard);
+        for (offset, (source, _)) in merged.sources.into_iter().enumerate() {
+            self.axes.insert(index + 1 + offset, source);
+        }
+        true
+    }
     pub fn pan_begin(&mut self, position: Point<Pixels>) {
         if self.panning {
             return;
@@ -126,6 +337,14 @@ impl PlotModel {
             axes.write().zoom_begin(position);
         }
     }
+    /// The pivot of a rubberband zoom whose drag starts at `start`, see `rubberband_anchor`
+    pub fn rubberband_pivot(&self, start: Point<Pixels>) -> Point<Pixels> {
+        match self.rubberband_anchor {
+            RubberbandAnchor::DragStart => start,
+            RubberbandAnchor::Center => self.bounds.center(),
+            RubberbandAnchor::Fixed(offset) => self.bounds.origin + offset,
+        }
+    }
     pub fn zoom(&mut self, factor: f64) {
         if !self.zooming {
             return;
@@ -134,6 +353,14 @@ impl PlotModel {
             axes.write().zoom(factor);
         }
     }
+    pub fn zoom_xy(&mut self, factor_x: f64, factor_y: f64) {
+        if !self.zooming {
+            return;
+        }
+        for axes in self.axes.iter_mut() {
+            axes.write().zoom_xy(factor_x, factor_y);
+        }
+    }
     pub fn zoom_end(&mut self) {
         if !self.zooming {
             return;
@@ -143,6 +370,69 @@ impl PlotModel {
             axes.write().zoom_end();
         }
     }
+    pub fn zoom_to_pixel_bounds(&mut self, bounds: Bounds<Pixels>) {
+        for axes in self.axes.iter_mut() {
+            axes.write().zoom_to_pixel_bounds(bounds);
+        }
+    }
+    /// Show `bounds` on every `AxesModel<X, Y>` of this plot, see `AxesModel::zoom_to`
+    pub fn zoom_to<X: AxisType, Y: AxisType>(&mut self, bounds: AxesBounds<X, Y>, padding: f64) {
+        for axes in self.axes.iter_mut() {
+            if let Some(model) = downcast_axes::<X, Y>(&mut *axes.write()) {
+                model.zoom_to(bounds, padding);
+            }
+        }
+    }
+    /// Call `f` with every series on every axes of this plot
+    pub fn for_each_series(&mut self, mut f: impl FnMut(&mut dyn Series)) {
+        for axes in self.axes.iter_mut() {
+            axes.write().for_each_series(&mut f);
+        }
+    }
+    /// Append the point under `position` to `edit_target`. Returns whether it was added
+    pub fn add_point_at(&mut self, position: Point<Pixels>) -> bool {
+        let Some(target) = self.edit_target else {
+            return false;
+        };
+        let Some(axes) = self.axes.get(target.axes) else {
+            return false;
+        };
+        axes.write().add_point_at(target.element, position)
+    }
+    pub fn brush_begin(&mut self, x: Pixels) {
+        self.brush = Some((x, x));
+    }
+    /// Move the free end of the brush and report the range to every axes
+    pub fn brush_to(&mut self, x: Pixels) {
+        let Some((start, end)) = self.brush.as_mut() else {
+            return;
+        };
+        *end = x;
+        let (start, end) = (*start, *end);
+        for axes in self.axes.iter_mut() {
+            axes.write().brush(start, end);
+        }
+    }
+    pub fn clear_brush(&mut self) {
+        self.brush = None;
+    }
+    /// Returns whether any axes needs a repaint, see `Axes::hover`
+    pub fn hover(&mut self, position: Option<Point<Pixels>>) -> bool {
+        let mut changed = false;
+        for axes in self.axes.iter_mut() {
+            changed |= axes.write().hover(position);
+        }
+        changed
+    }
+    /// Call `f` once the pointer leaves the plot, e.g. to clear a cursor synchronized elsewhere.
+    /// It runs with the model unlocked, so it may lock this plot again
+    pub fn on_leave(&mut self, f: impl FnMut() + Send + 'static) {
+        self.on_leave = Some(Box::new(f));
+    }
+}
+
+fn downcast_axes<X: AxisType, Y: AxisType>(axes: &mut dyn Axes) -> Option<&mut AxesModel<X, Y>> {
+    (axes as &mut dyn Any).downcast_mut()
 }
 
 #[derive(Clone)]
@@ -150,7 +440,15 @@ pub struct PlotView {
     pub model: Arc<RwLock<PlotModel>>,
     pub last_zoom_ts: Option<Instant>,
     pub acc_zoom_in: f64,
+    /// Where a right-drag or Alt+left-drag zoom started
     pub last_zoom_rb: Option<Point<Pixels>>,
+    /// Whether the rubberband zoom in progress scales the axes independently, see `alt_drag_zoom`
+    pub zoom_rb_2d: bool,
+    /// Start and current corner of a Shift+drag box zoom in progress
+    pub box_zoom: Option<(Point<Pixels>, Point<Pixels>)>,
+    /// Where the left button went down, to tell clicks from pans
+    click_start: Option<Point<Pixels>>,
+    redraw_pending: bool,
 }
 impl PlotView {
     pub fn new(model: Arc<RwLock<PlotModel>>) -> Self {
@@ -159,12 +457,19 @@ impl PlotView {
             last_zoom_ts: None,
             acc_zoom_in: 0.0,
             last_zoom_rb: None,
+            zoom_rb_2d: false,
+            box_zoom: None,
+            click_start: None,
+            redraw_pending: false,
         }
     }
 
+    /// End the scroll zoom gesture once it has been idle for `zoom_idle_timeout`,
+    /// resetting `acc_zoom_in` for the next one
     fn try_clean_zoom(&mut self) {
         if let Some(last_time) = self.last_zoom_ts {
-            if last_time.elapsed() > Duration::from_secs_f32(0.2) {
+            let timeout = self.model.read().zoom_idle_timeout;
+            if last_time.elapsed() > timeout {
                 self.model.write().zoom_end();
                 self.last_zoom_ts = None;
                 self.acc_zoom_in = 0.0;
@@ -199,24 +504,81 @@ impl PlotView {
         let Some(last_zoom_point) = self.last_zoom_rb else {
             return;
         };
-        let delta = zoom_point.y - last_zoom_point.y;
-        let zoom_in = -delta.0 as f64 * self.model.read().zoom_rubberband_precision;
-        let factor = zoom_in.exp();
-        self.model.write().zoom(factor);
+        let delta = zoom_point - last_zoom_point;
+        let precision = self.model.read().zoom_rubberband_precision;
+        let factor_y = (-delta.y.0 as f64 * precision).exp();
+        if self.zoom_rb_2d {
+            // dragging right zooms in on x, like dragging down does on y
+            let factor_x = (-delta.x.0 as f64 * precision).exp();
+            self.model.write().zoom_xy(factor_x, factor_y);
+        } else {
+            self.model.write().zoom(factor_y);
+        }
         cx.notify()
     }
+    /// The box being dragged, once it is past the minimum drag
+    fn box_zoom_bounds(&self) -> Option<Bounds<Pixels>> {
+        let (start, end) = self.box_zoom?;
+        let min = point(px(start.x.0.min(end.x.0)), px(start.y.0.min(end.y.0)));
+        let max = point(px(start.x.0.max(end.x.0)), px(start.y.0.max(end.y.0)));
+        if max.x - min.x < BOX_ZOOM_MIN_DRAG || max.y - min.y < BOX_ZOOM_MIN_DRAG {
+            return None;
+        }
+        Some(Bounds::from_corners(min, max))
+    }
+    /// Keep an animated plot redrawing, no faster than `max_fps` if set
+    fn schedule_redraw(&mut self, cx: &mut Context<Self>) {
+        let mut model = self.model.write();
+        if !model.animated || model.paused {
+            return;
+        }
+        let Some(max_fps) = model.max_fps else {
+            model.fps.next_fps();
+            let id = cx.entity_id();
+            cx.defer(move |app| app.notify(id));
+            return;
+        };
+        if self.redraw_pending {
+            return;
+        }
+        let interval = Duration::from_secs_f32(1.0 / max_fps.max(f32::EPSILON));
+        let delay = interval.saturating_sub(model.fps.last_frame.elapsed());
+        model.fps.next_fps();
+        self.redraw_pending = true;
+        cx.spawn(async move |this, cx| {
+            Timer::after(delay).await;
+            this.update(cx, |this, cx| {
+                this.redraw_pending = false;
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+    /// Pause or resume an animated plot, repainting once so the change shows
+    pub fn set_paused(&mut self, paused: bool, cx: &mut Context<Self>) {
+        self.model.write().paused = paused;
+        cx.notify();
+    }
 }
 impl Render for PlotView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<'_, Self>) -> impl IntoElement {
         self.try_clean_zoom();
+        self.schedule_redraw(cx);
         let len = self.model.read().axes.len();
         for axes in 0..len {
             let axes = self.model.read().axes[axes].clone();
             axes.write().new_render();
         }
 
+        // content masks are rectangular, so inset the content until its corners touch the arcs
+        let corner_radius = self.model.read().corner_radius;
+        let corner_inset = corner_radius * (1.0 - std::f32::consts::FRAC_1_SQRT_2);
         div()
             .size_full()
+            .rounded(corner_radius)
+            .overflow_hidden()
+            .p(corner_inset)
             .child(
                 canvas(|_, _window, _cx| (), {
                     let this = self.clone();
@@ -225,14 +587,66 @@ impl Render for PlotView {
                         for axes in this.model.write().axes.iter_mut() {
                             axes.write().render(bounds, window, cx);
                         }
+                        if let Some((start, end)) = this.model.read().brush {
+                            let band = Bounds::from_corners(
+                                point(px(start.0.min(end.0)), bounds.top()),
+                                point(px(start.0.max(end.0)), bounds.bottom()),
+                            );
+                            window.paint_quad(fill(band, gpui::blue().opacity(0.1)));
+                        }
+                        if let Some(rect) = this.box_zoom_bounds() {
+                            window.paint_quad(
+                                fill(rect, gpui::blue().opacity(0.15))
+                                    .border_widths(Edges::all(px(1.0)))
+                                    .border_color(gpui::blue()),
+                            );
+                        }
                     }
                 })
                 .size_full(),
             )
+            .id("plot")
+            .on_hover(cx.listener(|this, hovered: &bool, _window, cx| {
+                if !*hovered {
+                    // clear what follows the pointer, then call back without holding the lock
+                    let callback = {
+                        let mut model = this.model.write();
+                        model.hover(None);
+                        model.on_leave.take()
+                    };
+                    if let Some(mut callback) = callback {
+                        callback();
+                        let mut model = this.model.write();
+                        // unless the callback set a new one
+                        if model.on_leave.is_none() {
+                            model.on_leave = Some(callback);
+                        }
+                    }
+                    cx.notify();
+                }
+            }))
             .on_mouse_down(
                 MouseButton::Left,
                 cx.listener(|this, ev: &MouseDownEvent, _window, _cx| {
+                    if ev.modifiers.shift {
+                        this.box_zoom = Some((ev.position, ev.position));
+                        return;
+                    }
+                    if ev.modifiers.alt && this.model.read().alt_drag_zoom {
+                        this.try_clean_zoom();
+                        this.last_zoom_rb = Some(ev.position);
+                        this.zoom_rb_2d = true;
+                        let mut model = this.model.write();
+                        let pivot = model.rubberband_pivot(ev.position);
+                        model.zoom_begin(pivot);
+                        return;
+                    }
                     let mut model = this.model.write();
+                    if model.brush_mode {
+                        model.brush_begin(ev.position.x);
+                        return;
+                    }
+                    this.click_start = Some(ev.position);
                     model.pan_begin(ev.position);
                 }),
             )
@@ -241,28 +655,65 @@ impl Render for PlotView {
                 cx.listener(|this, ev: &MouseDownEvent, _window, _cx| {
                     this.try_clean_zoom();
                     this.last_zoom_rb = Some(ev.position);
-                    this.model.write().zoom_begin(ev.position);
+                    this.zoom_rb_2d = false;
+                    let mut model = this.model.write();
+                    let pivot = model.rubberband_pivot(ev.position);
+                    model.zoom_begin(pivot);
                 }),
             )
             .on_mouse_move(cx.listener(|this, ev: &MouseMoveEvent, window, cx| {
                 match ev.pressed_button {
+                    Some(MouseButton::Left) if this.last_zoom_rb.is_some() => {
+                        this.zoom_rubberband(ev.position, window, cx);
+                    }
                     Some(MouseButton::Left) => {
-                        let mut model = this.model.write();
-                        model.pan(ev);
+                        if let Some((_, end)) = this.box_zoom.as_mut() {
+                            *end = ev.position;
+                        } else if this.model.read().brush_mode {
+                            this.model.write().brush_to(ev.position.x);
+                        } else {
+                            this.model.write().pan(ev);
+                        }
                         cx.notify();
                     }
-                    // it won't work on MacOS
+                    // it won't work on MacOS, where Alt+left-drag does the same
                     Some(MouseButton::Right) => {
                         this.zoom_rubberband(ev.position, window, cx);
                     }
+                    None => {
+                        if this.model.write().hover(Some(ev.position)) {
+                            cx.notify();
+                        }
+                    }
                     _ => {}
                 }
             }))
             .on_mouse_up(
                 MouseButton::Left,
-                cx.listener(|this, _ev, _window, _cx| {
+                cx.listener(|this, ev: &MouseUpEvent, _window, cx| {
+                    if this.box_zoom.is_some() {
+                        if let Some(bounds) = this.box_zoom_bounds() {
+                            this.model.write().zoom_to_pixel_bounds(bounds);
+                        }
+                        this.box_zoom = None;
+                        cx.notify();
+                        return;
+                    }
+                    if this.last_zoom_rb.take().is_some() {
+                        this.model.write().zoom_end();
+                        cx.notify();
+                        return;
+                    }
                     let mut model = this.model.write();
                     model.pan_end();
+                    if let Some(start) = this.click_start.take() {
+                        let moved = ev.position - start;
+                        let is_click = moved.x.0.abs() < CLICK_MAX_DRAG.0
+                            && moved.y.0.abs() < CLICK_MAX_DRAG.0;
+                        if is_click && model.add_point_at(ev.position) {
+                            cx.notify();
+                        }
+                    }
                 }),
             )
             .on_mouse_up(
@@ -279,12 +730,22 @@ impl Render for PlotView {
                     ScrollDelta::Pixels(p) => {
                         // println!("Scroll event captured: {:?}", p);
                         // Swipe swipe down to zoom in. This is aligned with Google Maps and some tools like Mac Mouse Fix or Scroll Inverter
-                        -p.y.0 as f64 * model.zoom_swipe_precision
+                        let zoom_in = -p.y.0 as f64 * model.zoom_swipe_precision;
+                        if model.invert_swipe_zoom {
+                            -zoom_in
+                        } else {
+                            zoom_in
+                        }
                     }
                     ScrollDelta::Lines(l) => {
                         // println!("Scroll event in lines {:?}, ignoring.",&q);
                         // Scroll up to zoom in
-                        l.y as f64 * model.zoom_scroll_precision
+                        let zoom_in = l.y as f64 * model.zoom_scroll_precision;
+                        if model.invert_scroll_zoom {
+                            -zoom_in
+                        } else {
+                            zoom_in
+                        }
                     }
                 };
                 drop(model);
@@ -1,6 +1,121 @@
 use crate::figure::axes::AxesModel;
-use crate::geometry::{point2, AxisType, GeometryPixels, Text};
-use gpui::{px, App, Bounds, Pixels, Window};
+use crate::geometry::{
+    measure_text_in, point2, truncate_to_width_in, AxisType, GeometryPixels, Text,
+};
+use crate::utils::math::display_double_smartly;
+use gpui::{px, App, Bounds, Edges, Pixels, Window};
+
+/// Space between the plotting area and the tick labels
+const TICK_LABEL_GAP: Pixels = px(3.0);
+/// Space kept free beyond the tick labels
+const TICK_LABEL_PADDING: Pixels = px(4.0);
+
+const SI_PREFIXES: [&str; 5] = ["", "k", "M", "G", "T"];
+
+/// How the integer part of numeric tick labels is split into groups of three digits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigitGrouping {
+    /// `1000000.5`
+    #[default]
+    None,
+    /// `1,000,000.5`
+    Comma,
+    /// `1.000.000,5`, with a decimal comma
+    Period,
+    /// `1 000 000.5`
+    Space,
+}
+impl DigitGrouping {
+    /// The group separator and the decimal separator
+    fn separators(&self) -> Option<(char, char)> {
+        match self {
+            DigitGrouping::None => None,
+            DigitGrouping::Comma => Some((',', '.')),
+            DigitGrouping::Period => Some(('.', ',')),
+            DigitGrouping::Space => Some((' ', '.')),
+        }
+    }
+}
+
+/// How the tick values of an axis are turned into labels
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TickFormat {
+    /// Divide all values by a shared power of 1000 and append its SI prefix, e.g. `2.5M`
+    pub auto_si_prefix: bool,
+    /// Only applies to labels that are plain numbers, dates and times are left as they are
+    pub digit_grouping: DigitGrouping,
+}
+impl TickFormat {
+    pub fn auto_si_prefix(mut self, auto_si_prefix: bool) -> Self {
+        self.auto_si_prefix = auto_si_prefix;
+        self
+    }
+    pub fn digit_grouping(mut self, digit_grouping: DigitGrouping) -> Self {
+        self.digit_grouping = digit_grouping;
+        self
+    }
+    /// Format `values` consistently, sharing one scale across all of them
+    pub fn format_all<T: AxisType>(&self, values: &[T]) -> Vec<String> {
+        let labels = self.format_scaled(values);
+        let Some((group, decimal)) = self.digit_grouping.separators() else {
+            return labels;
+        };
+        labels
+            .iter()
+            .map(|label| group_digits(label, group, decimal))
+            .collect()
+    }
+    fn format_scaled<T: AxisType>(&self, values: &[T]) -> Vec<String> {
+        if !self.auto_si_prefix {
+            return values.iter().map(|value| value.format()).collect();
+        }
+        let max = values
+            .iter()
+            .map(|value| value.to_f64().abs())
+            .fold(0.0, f64::max);
+        let index = if max >= 1.0 {
+            ((max.log10() / 3.0).floor() as usize).min(SI_PREFIXES.len() - 1)
+        } else {
+            0
+        };
+        let divisor = 1000f64.powi(index as i32);
+        values
+            .iter()
+            .map(|value| {
+                // round away the noise of the division so labels stay short
+                let scaled = (value.to_f64() / divisor * 1e6).round() / 1e6;
+                format!("{}{}", display_double_smartly(scaled), SI_PREFIXES[index])
+            })
+            .collect()
+    }
+}
+/// Insert `group` between every three digits of the integer part of `label` and use `decimal`
+/// as its decimal point. Labels that aren't numbers, optionally with an SI prefix, are kept
+fn group_digits(label: &str, group: char, decimal: char) -> String {
+    let number = label.trim_end_matches(|c: char| SI_PREFIXES.iter().any(|p| p.starts_with(c)));
+    if number.parse::<f64>().is_err() {
+        return label.to_string();
+    }
+    let suffix = &label[number.len()..];
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    let integer_len = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    let (integer, rest) = unsigned.split_at(integer_len);
+    let mut grouped = String::from(sign);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer_len - i) % 3 == 0 {
+            grouped.push(group);
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(&rest.replace('.', &decimal.to_string()));
+    grouped.push_str(suffix);
+    grouped
+}
 
 #[derive(Clone)]
 pub struct TicksView<'a, X: AxisType, Y: AxisType> {
@@ -10,36 +125,73 @@ impl<'a, X: AxisType, Y: AxisType> TicksView<'a, X, Y> {
     pub fn new(context: &'a AxesModel<X, Y>) -> Self {
         Self { context }
     }
+    fn x_labels(&self, window: &Window) -> Vec<String> {
+        let context = self.context;
+        let labels = context.x_tick_format.format_all(&context.grid.grid_x_lines);
+        self.truncate(window, labels)
+    }
+    fn y_labels(&self, window: &Window) -> Vec<String> {
+        let context = self.context;
+        let labels = context.y_tick_format.format_all(&context.grid.grid_y_lines);
+        self.truncate(window, labels)
+    }
+    fn truncate(&self, window: &Window, labels: Vec<String>) -> Vec<String> {
+        let context = self.context;
+        let Some(max_width) = context.tick_label_max_width else {
+            return labels;
+        };
+        labels
+            .iter()
+            .map(|text| {
+                let family = context.font_family.as_ref();
+                truncate_to_width_in(window, text, context.tick_font_size, max_width, family)
+            })
+            .collect()
+    }
+    /// Space needed around the plotting area to fit the tick labels at the current font size
+    pub fn margins(&self, window: &Window) -> Edges<Pixels> {
+        let context = self.context;
+        let size = context.tick_font_size;
+        let family = context.font_family.as_ref();
+        let measure = |text: String| measure_text_in(window, &text, size, family).width.0;
+        let x_labels = self.x_labels(window);
+        let y_labels = self.y_labels(window);
+        let max_x_width = px(x_labels.into_iter().map(measure).fold(0.0, f32::max));
+        let max_y_width = px(y_labels.into_iter().map(measure).fold(0.0, f32::max));
+        Edges {
+            top: size / 2.0,
+            right: max_x_width / 2.0 + TICK_LABEL_PADDING,
+            bottom: size + TICK_LABEL_GAP + TICK_LABEL_PADDING,
+            left: max_y_width + TICK_LABEL_GAP + TICK_LABEL_PADDING,
+        }
+    }
     pub fn render(&mut self, window: &mut Window, cx: &mut App, pixel_bounds: Bounds<Pixels>) {
         let context = self.context;
-        let size = px(12.0);
+        let size = context.tick_font_size;
+        let family = context.font_family.as_ref();
+        let label = |origin, text| Text {
+            font_family: family.cloned(),
+            ..Text::new(origin, size, text)
+        };
 
-        for x in context.grid.grid_x_lines.iter().cloned() {
-            let text = x.format();
-            let x_px = context.axes_bounds.x.transform(context.pixel_bounds.x, x)
-                - size * text.len() / 2.0 * 0.5;
-            let y_px = context.pixel_bounds.max_y() + px(3.0);
-            Text {
-                origin: point2(x_px, y_px),
-                size,
-                text,
-            }
-            .render(window, cx, Some(pixel_bounds));
+        let x_labels = self.x_labels(window);
+        for (x, text) in context.grid.grid_x_lines.iter().cloned().zip(x_labels) {
+            let width = measure_text_in(window, &text, size, family).width;
+            let x_px = context.axes_bounds.x.transform(context.pixel_bounds.x, x) - width / 2.0;
+            let y_px = context.pixel_bounds.max_y() + TICK_LABEL_GAP;
+            label(point2(x_px, y_px), text).render(window, cx, Some(pixel_bounds));
         }
-        for y in context.grid.grid_y_lines.iter().cloned() {
-            let text = y.format();
-
-            let x_px = context.pixel_bounds.min_x() - size * text.len() as f32 * 0.5 - px(3.0);
+        let y_labels = self.y_labels(window);
+        for (y, text) in context.grid.grid_y_lines.iter().cloned().zip(y_labels) {
+            // right-align against the axis, the left margin is sized to fit the widest label
+            let width = measure_text_in(window, &text, size, family).width;
+            let x_px = context.pixel_bounds.min_x() - width - TICK_LABEL_GAP;
             let y_px = context.axes_bounds.y.transform(context.pixel_bounds.y, y) - size / 2.0;
-            Text {
-                origin: point2(x_px, y_px),
-                size,
-                text,
-            }
-            .render(window, cx, Some(pixel_bounds));
+            label(point2(x_px, y_px), text).render(window, cx, Some(pixel_bounds));
         }
     }
 }
+
 impl<'a, X: AxisType, Y: AxisType> GeometryPixels for TicksView<'a, X, Y> {
     fn render_pixels(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         self.render(window, cx, bounds);
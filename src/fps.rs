@@ -4,6 +4,8 @@ use std::time::Instant;
 pub struct FpsModel {
     pub fps: f32,
     pub last_time: Instant,
+    /// When the last frame was counted
+    pub last_frame: Instant,
     pub last_fps: f64,
     pub frame_count: f32,
 }
@@ -17,6 +19,7 @@ impl FpsModel {
         Self {
             fps: 0.0,
             last_time: Instant::now(),
+            last_frame: Instant::now(),
             last_fps: 0.0,
             frame_count: 0.0,
         }
@@ -25,6 +28,7 @@ impl FpsModel {
         let now = Instant::now();
         let delta = now - self.last_time;
         self.frame_count += 1.0;
+        self.last_frame = now;
         if delta.as_secs_f32() >= 1.0 {
             self.fps = self.frame_count / delta.as_secs_f32();
             self.frame_count = 0.0;
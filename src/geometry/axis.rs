@@ -1,5 +1,5 @@
 use crate::geometry::point::Point2;
-use crate::utils::math::display_double_smartly;
+use crate::utils::math::{display_double_smartly, nice_step};
 use chrono::{NaiveDate, Timelike};
 use gpui::{point, px, Bounds, Pixels, Point, Size};
 use std::cmp::Ordering;
@@ -22,7 +22,14 @@ pub trait AxisType:
     fn format(&self) -> String;
     fn to_f64(&self) -> f64;
     fn from_f64(value: f64) -> Self;
+    /// Half the span shown around this value when all the data shares it, in the `to_f64`
+    /// units of `Self::Delta`, which ranges measure their offsets in
+    fn degenerate_half_span(&self) -> f64 {
+        (self.to_f64().abs() * 0.05).max(0.5)
+    }
 }
+/// One second in nanoseconds, the `to_f64` unit of the date, time and duration deltas
+const SECOND_NANOS: f64 = 1_000_000_000.0;
 impl AxisType for f32 {
     type Delta = f32;
     fn format(&self) -> String {
@@ -64,6 +71,10 @@ impl AxisType for NaiveDate {
         let date = chrono::DateTime::from_timestamp_nanos(timestamp);
         date.date_naive()
     }
+    /// A day. The numeric default would be 5% of the time since the epoch, which spans years
+    fn degenerate_half_span(&self) -> f64 {
+        86_400.0 * SECOND_NANOS
+    }
 }
 
 impl AxisType for chrono::NaiveDateTime {
@@ -79,6 +90,9 @@ impl AxisType for chrono::NaiveDateTime {
         let date = chrono::DateTime::from_timestamp_nanos(timestamp);
         date.naive_utc()
     }
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
 }
 impl AxisType for chrono::NaiveTime {
     type Delta = chrono::Duration;
@@ -94,6 +108,9 @@ impl AxisType for chrono::NaiveTime {
         let time = chrono::NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanoseconds);
         time.expect("out of range")
     }
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
 }
 impl AxisType for chrono::DateTime<chrono::Utc> {
     type Delta = chrono::Duration;
@@ -107,6 +124,42 @@ impl AxisType for chrono::DateTime<chrono::Utc> {
         let timestamp = value as i64;
         chrono::DateTime::<chrono::Utc>::from_timestamp_nanos(timestamp)
     }
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
+}
+impl AxisType for chrono::DateTime<chrono::Local> {
+    type Delta = chrono::Duration;
+    fn format(&self) -> String {
+        self.to_string()
+    }
+    fn to_f64(&self) -> f64 {
+        self.timestamp_nanos_opt().unwrap() as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        let timestamp = value as i64;
+        chrono::DateTime::from_timestamp_nanos(timestamp).with_timezone(&chrono::Local)
+    }
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
+}
+/// The offset is carried along by arithmetic, values created with `from_f64` are in UTC
+impl AxisType for chrono::DateTime<chrono::FixedOffset> {
+    type Delta = chrono::Duration;
+    fn format(&self) -> String {
+        self.to_string()
+    }
+    fn to_f64(&self) -> f64 {
+        self.timestamp_nanos_opt().unwrap() as f64
+    }
+    fn from_f64(value: f64) -> Self {
+        let timestamp = value as i64;
+        chrono::DateTime::from_timestamp_nanos(timestamp).fixed_offset()
+    }
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
 }
 impl AxisType for chrono::Duration {
     type Delta = chrono::Duration;
@@ -119,6 +172,9 @@ impl AxisType for chrono::Duration {
     fn from_f64(value: f64) -> Self {
         chrono::Duration::nanoseconds(value as i64)
     }
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
 }
 
 impl AxisType for std::time::Duration {
@@ -134,6 +190,10 @@ impl AxisType for std::time::Duration {
     fn from_f64(value: f64) -> Self {
         std::time::Duration::from_nanos(value as u64)
     }
+
+    fn degenerate_half_span(&self) -> f64 {
+        SECOND_NANOS
+    }
 }
 
 /// more for internal use
@@ -156,6 +216,9 @@ pub struct AxisRangePixels {
     min: Pixels,
     max: Pixels,
     size: f64,
+    /// Where the range starts and how far it extends, `min` and `size` unless padded
+    data_min: Pixels,
+    data_size: f64,
     pub(crate) pixels_per_element: f64,
 }
 impl AxisRangePixels {
@@ -164,9 +227,21 @@ impl AxisRangePixels {
             min,
             max,
             size,
+            data_min: min,
+            data_size: size,
             pixels_per_element: f64::NAN,
         }
     }
+    /// Keep `low` and `high` times the range free at the start and end, in fractions of the range
+    pub(crate) fn inset(&mut self, low: f64, high: f64) {
+        let total = 1.0 + low + high;
+        if total <= 0.0 || !total.is_finite() {
+            return;
+        }
+        let direction = if self.max >= self.min { 1.0 } else { -1.0 };
+        self.data_min = self.min + px((direction * self.size * low / total) as f32);
+        self.data_size = self.size / total;
+    }
 }
 impl Add<Pixels> for AxisRangePixels {
     type Output = Self;
@@ -176,6 +251,8 @@ impl Add<Pixels> for AxisRangePixels {
             min: self.min + rhs,
             max: self.max + rhs,
             size: self.size,
+            data_min: self.data_min + rhs,
+            data_size: self.data_size,
             pixels_per_element: self.pixels_per_element,
         }
     }
@@ -222,7 +299,8 @@ impl<T: AxisType> AxisRange<T> {
         self.min()..self.max()
     }
     pub fn new(min: T, max: T) -> Self {
-        let base = T::from_f64((max - min).to_f64() / 2.0);
+        // derive the base from `min` so that values like time zones carry over
+        let base = min + T::Delta::from_f64((max - min).to_f64() / 2.0);
         Self::new_with_base(base, min, max)
     }
     pub fn new_with_base(base: T, min: T, max: T) -> Self {
@@ -247,6 +325,15 @@ impl<T: AxisType> AxisRange<T> {
         self.min_to_base = midpoint - size / 2.0;
         self.max_to_base = midpoint + size / 2.0;
     }
+    /// Expand the range outward so both ends land on multiples of `step`
+    pub fn snap_outward(&mut self, step: f64) {
+        if !step.is_finite() || step <= 0.0 {
+            return;
+        }
+        let base = self.base.to_f64();
+        self.min_to_base = ((base + self.min_to_base) / step).floor() * step - base;
+        self.max_to_base = ((base + self.max_to_base) / step).ceil() * step - base;
+    }
     pub fn set_min(&mut self, min: T) {
         self.min_to_base = (min - self.base).to_f64();
     }
@@ -261,51 +348,162 @@ impl<T: AxisType> AxisRange<T> {
     }
 
     pub fn contains(&self, value: T) -> bool {
-        value >= self.min() && value <= self.max()
+        let range = self.ascending();
+        value >= range.min() && value <= range.max()
+    }
+    /// Whether `min` lies above `max`, so that values decrease along the axis,
+    /// e.g. for time running leftward
+    pub fn is_descending(&self) -> bool {
+        self.max_to_base < self.min_to_base
+    }
+    /// The same range with `min` and `max` swapped
+    pub fn reversed(&self) -> Self {
+        Self {
+            base: self.base,
+            min_to_base: self.max_to_base,
+            max_to_base: self.min_to_base,
+        }
+    }
+    fn ascending(&self) -> Self {
+        if self.is_descending() {
+            self.reversed()
+        } else {
+            *self
+        }
     }
     pub fn size_in_f64(&self) -> f64 {
         self.max_to_base - self.min_to_base
     }
+    /// The value halfway between `min` and `max`
+    pub fn center(&self) -> T {
+        self.base + T::Delta::from_f64((self.min_to_base + self.max_to_base) / 2.0)
+    }
+    /// `max - min`, negative on a descending range if the delta type allows it
+    pub fn span(&self) -> T::Delta {
+        T::Delta::from_f64(self.size_in_f64())
+    }
 
+    /// A zero-size range maps as if it spanned 1, so empty bounds don't produce NaN
     pub fn pixels_per_element(&self, bounds: AxisRangePixels) -> f64 {
-        bounds.size / self.size_in_f64()
+        let size = self.size_in_f64();
+        if size == 0.0 || !size.is_finite() {
+            return bounds.data_size;
+        }
+        bounds.data_size / size
     }
 
     pub fn elements_per_pixels(&self, delta: Pixels, bounds: AxisRangePixels) -> f64 {
-        delta.0 as f64 * self.size_in_f64() / bounds.size
+        delta.0 as f64 * self.size_in_f64() / bounds.data_size
     }
     /// Transform a value from the range `[min, max]` to the range `[bounds.min, bounds.max]`
     pub fn transform(&self, bounds: AxisRangePixels, value: T) -> Pixels {
         let adjusted_pixels =
-            (value - self.min()).to_f64() * bounds.pixels_per_element + bounds.min.0 as f64;
+            (value - self.min()).to_f64() * bounds.pixels_per_element + bounds.data_min.0 as f64;
         Pixels(adjusted_pixels as f32)
     }
 
+    /// `transform` as `(scale, offset)` with `pixels = value.to_f64() * scale + offset`
+    pub fn linear_transform(&self, bounds: AxisRangePixels) -> (f64, f64) {
+        let scale = bounds.pixels_per_element;
+        (
+            scale,
+            bounds.data_min.0 as f64 - self.min().to_f64() * scale,
+        )
+    }
+    /// Set the range so that `transform` matches `pixels = value.to_f64() * scale + offset`
+    pub fn set_linear_transform(&mut self, bounds: AxisRangePixels, scale: f64, offset: f64) {
+        let direction = if bounds.max >= bounds.min { 1.0 } else { -1.0 };
+        let data_min = bounds.data_min.0 as f64;
+        let min = (data_min - offset) / scale;
+        let max = (data_min + direction * bounds.data_size - offset) / scale;
+        *self = Self::new(T::from_f64(min), T::from_f64(max));
+    }
     pub fn transform_reverse_f64(&self, bounds: AxisRangePixels, value: f64) -> f64 {
-        self.min_to_base + (value - bounds.min.0 as f64) / bounds.pixels_per_element
+        self.min_to_base + (value - bounds.data_min.0 as f64) / bounds.pixels_per_element
+    }
+    /// Transform a pixel position back into a value in the range
+    pub fn transform_reverse(&self, bounds: AxisRangePixels, value: Pixels) -> T {
+        self.base + T::Delta::from_f64(self.transform_reverse_f64(bounds, value.0 as f64))
     }
+    /// Walk from `min` towards `max` by the magnitude of `step`, downward on a descending range.
+    /// Yields nothing for a zero or non-finite step, and ends early on a step too small to
+    /// change the value
     pub fn iter_step_by(&self, step: T::Delta) -> impl Iterator<Item = T> + '_ {
-        let mut current = self.min();
+        let descending = self.is_descending();
+        let magnitude = step.to_f64().abs();
+        let valid = magnitude.is_finite() && magnitude > 0.0;
+        let step = T::Delta::from_f64(magnitude);
+        let mut current = valid.then(|| self.min());
         std::iter::from_fn(move || {
-            if current > self.max() {
-                return None;
+            let result = current?;
+            if descending {
+                if result < self.max() {
+                    return None;
+                }
+                // subtract only while it stays in range, unsigned deltas can't go below zero
+                current = (result.to_f64() - magnitude >= self.max().to_f64())
+                    .then(|| result - step)
+                    .filter(|next| *next < result);
+            } else {
+                if result > self.max() {
+                    return None;
+                }
+                // a step lost in rounding would never get past the end
+                current = Some(result + step).filter(|next| *next > result);
             }
-            let result = current;
-            current = current + step;
             Some(result)
         })
     }
+    /// `iter_step_by` with the step given in the units of `to_f64`
     pub fn iter_step_by_f64(&self, step: f64) -> impl Iterator<Item = T> + '_ {
-        let mut current = self.min_to_base;
+        let descending = self.is_descending();
+        let valid = step.is_finite() && step != 0.0;
+        let step = if descending { -step.abs() } else { step.abs() };
+        let mut current = valid.then_some(self.min_to_base);
         std::iter::from_fn(move || {
-            if current > self.max_to_base {
+            let offset = current?;
+            let past_end = if descending {
+                offset < self.max_to_base
+            } else {
+                offset > self.max_to_base
+            };
+            if past_end {
                 return None;
             }
-            let result = self.base + T::Delta::from_f64(current);
-            current += step;
-            Some(result)
+            // a step lost in rounding would never get past the end
+            current = Some(offset + step).filter(|next| *next != offset);
+            Some(self.base + T::Delta::from_f64(offset))
         })
     }
+    /// Whether the two ranges share at least one value
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (this, other) = (self.ascending(), other.ascending());
+        this.min() <= other.max() && other.min() <= this.max()
+    }
+    /// The part of this range that lies within `other`, if any
+    pub fn clip(&self, other: &Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let (this, other) = (self.ascending(), other.ascending());
+        let min = if this.min() < other.min() {
+            other.min()
+        } else {
+            this.min()
+        };
+        let max = if this.max() > other.max() {
+            other.max()
+        } else {
+            this.max()
+        };
+        let clipped = Self::new_with_base(self.base, min, max);
+        // keep the orientation of `self`
+        if self.is_descending() {
+            Some(clipped.reversed())
+        } else {
+            Some(clipped)
+        }
+    }
     pub fn union(&self, other: &Self) -> Option<Self> {
         let base = match self.base.partial_cmp(&other.base)? {
             Ordering::Less => self.base,
@@ -396,7 +594,35 @@ impl<X: AxisType, Y: AxisType> AxesBounds<X, Y> {
             y: self.y.transform(bounds.y, point.y),
         }
     }
+    /// Transform many points at once, appending them to `out`
+    pub fn transform_points(
+        &self,
+        bounds: AxesBoundsPixels,
+        points: &[Point2<X, Y>],
+        out: &mut Vec<Point<Pixels>>,
+    ) {
+        let min_x = self.x.min();
+        let min_y = self.y.min();
+        let (x_scale, x_offset) = (bounds.x.pixels_per_element, bounds.x.data_min.0 as f64);
+        let (y_scale, y_offset) = (bounds.y.pixels_per_element, bounds.y.data_min.0 as f64);
+        out.reserve(points.len());
+        out.extend(points.iter().map(|p| Point {
+            x: Pixels(((p.x - min_x).to_f64() * x_scale + x_offset) as f32),
+            y: Pixels(((p.y - min_y).to_f64() * y_scale + y_offset) as f32),
+        }));
+    }
 
+    /// Transform a pixel position back into a point on the axes
+    pub fn transform_point_reverse(
+        &self,
+        bounds: AxesBoundsPixels,
+        p: Point<Pixels>,
+    ) -> Point2<X, Y> {
+        Point2::new(
+            self.x.transform_reverse(bounds.x, p.x),
+            self.y.transform_reverse(bounds.y, p.y),
+        )
+    }
     pub fn transform_point_reverse_f64(
         &self,
         bounds: AxesBoundsPixels,
@@ -418,7 +644,47 @@ impl<X: AxisType, Y: AxisType> AxesBounds<X, Y> {
     pub fn contains(&self, point: Point2<X, Y>) -> bool {
         self.x.contains(point.x) && self.y.contains(point.y)
     }
+    /// Whether the two rects overlap, e.g. to skip geometry outside the view
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.x.intersects(&other.x) && self.y.intersects(&other.y)
+    }
+    /// The part of this rect that lies within `rect`, if any
+    pub fn clip(&self, rect: &Self) -> Option<Self> {
+        Some(Self::new(self.x.clip(&rect.x)?, self.y.clip(&rect.y)?))
+    }
+}
+/// Bounds that fit `points`, padded on each side by `padding` times the span
+/// and snapped outward to nice round numbers. Empty input gives `0..1` on both axes
+pub fn nice_bounds(points: impl Iterator<Item = (f64, f64)>, padding: f64) -> AxesBounds<f64, f64> {
+    let mut min = point(f64::INFINITY, f64::INFINITY);
+    let mut max = point(f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for (x, y) in points {
+        if !x.is_finite() || !y.is_finite() {
+            continue;
+        }
+        min = point(min.x.min(x), min.y.min(y));
+        max = point(max.x.max(x), max.y.max(y));
+    }
+    if min.x > max.x {
+        return AxesBounds::new(AxisRange::new(0.0, 1.0), AxisRange::new(0.0, 1.0));
+    }
+    AxesBounds::new(
+        nice_range(min.x, max.x, padding),
+        nice_range(min.y, max.y, padding),
+    )
+}
+fn nice_range(min: f64, max: f64, padding: f64) -> AxisRange<f64> {
+    let span = max - min;
+    let (min, max) = if span == 0.0 {
+        (min - 0.5, max + 0.5)
+    } else {
+        (min - span * padding, max + span * padding)
+    };
+    let mut range = AxisRange::new(min, max);
+    range.snap_outward(nice_step((max - min) / 10.0));
+    range
 }
+
 // add Point<f64>
 impl<X: AxisType, Y: AxisType> Add<Size<f64>> for AxesBounds<X, Y> {
     type Output = Self;
@@ -429,3 +695,119 @@ impl<X: AxisType, Y: AxisType> Add<Size<f64>> for AxesBounds<X, Y> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_range(range: AxisRange<f64>, min: f64, max: f64) {
+        assert!(
+            (range.min() - min).abs() < 1e-9 && (range.max() - max).abs() < 1e-9,
+            "{:?}..{:?} is not {}..{}",
+            range.min(),
+            range.max(),
+            min,
+            max
+        );
+    }
+
+    #[test]
+    fn steps_up_an_ascending_range() {
+        let range = AxisRange::new(0.0, 1.0);
+        let expected = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+        assert_eq!(range.iter_step_by(0.25).collect::<Vec<_>>(), expected);
+        assert_eq!(range.iter_step_by(-0.25).collect::<Vec<_>>(), expected);
+        assert_eq!(range.iter_step_by_f64(0.25).collect::<Vec<_>>(), expected);
+        assert_eq!(range.iter_step_by_f64(-0.25).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn steps_down_a_descending_range() {
+        let range = AxisRange::new(1.0, 0.0);
+        let expected = vec![1.0, 0.75, 0.5, 0.25, 0.0];
+        assert_eq!(range.iter_step_by(0.25).collect::<Vec<_>>(), expected);
+        assert_eq!(range.iter_step_by(-0.25).collect::<Vec<_>>(), expected);
+        assert_eq!(range.iter_step_by_f64(0.25).collect::<Vec<_>>(), expected);
+        assert_eq!(range.iter_step_by_f64(-0.25).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn steps_down_descending_dates() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let range = AxisRange::new(day(4), day(1));
+        let dates: Vec<_> = range.iter_step_by(chrono::Duration::days(1)).collect();
+        assert_eq!(dates, vec![day(4), day(3), day(2), day(1)]);
+    }
+
+    #[test]
+    fn invalid_steps_yield_nothing() {
+        for range in [AxisRange::new(0.0, 1.0), AxisRange::new(1.0, 0.0)] {
+            for step in [0.0, f64::NAN, f64::INFINITY] {
+                assert_eq!(range.iter_step_by(step).take(10).count(), 0);
+                assert_eq!(range.iter_step_by_f64(step).take(10).count(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn steps_lost_in_rounding_end() {
+        // 1.0 is below the precision of the values, then of the offsets from the base
+        for range in [
+            AxisRange::new(1e18, 1e18 + 1e6),
+            AxisRange::new(1e18 + 1e6, 1e18),
+        ] {
+            assert_eq!(range.iter_step_by(1.0).take(10).count(), 1);
+        }
+        for range in [AxisRange::new(0.0, 1e18), AxisRange::new(1e18, 0.0)] {
+            assert_eq!(range.iter_step_by_f64(1.0).take(10).count(), 1);
+        }
+    }
+
+    #[test]
+    fn nice_bounds_snaps_to_round_numbers() {
+        let bounds = nice_bounds([(0.3, 1.2), (9.6, 97.0)].into_iter(), 0.0);
+        assert_range(bounds.x, 0.0, 10.0);
+        assert_range(bounds.y, 0.0, 100.0);
+    }
+
+    #[test]
+    fn nice_bounds_pads_before_snapping() {
+        let bounds = nice_bounds([(0.0, 0.0), (10.0, 100.0)].into_iter(), 0.1);
+        assert_range(bounds.x, -2.0, 12.0);
+        assert_range(bounds.y, -20.0, 120.0);
+    }
+
+    #[test]
+    fn nice_bounds_handles_negative_ranges() {
+        let bounds = nice_bounds([(-50.0, -3.0), (-10.0, -1.0)].into_iter(), 0.0);
+        assert_range(bounds.x, -50.0, -10.0);
+        assert_range(bounds.y, -3.0, -1.0);
+    }
+
+    #[test]
+    fn nice_bounds_widens_a_single_point() {
+        let bounds = nice_bounds([(3.0, -7.0)].into_iter(), 0.1);
+        assert_range(bounds.x, 2.5, 3.5);
+        assert_range(bounds.y, -7.5, -6.5);
+    }
+
+    #[test]
+    fn nice_bounds_skips_non_finite_points() {
+        let points = [
+            (f64::NAN, 1.0),
+            (0.0, 0.0),
+            (1.0, f64::INFINITY),
+            (1.0, 1.0),
+        ];
+        let bounds = nice_bounds(points.into_iter(), 0.0);
+        assert_range(bounds.x, 0.0, 1.0);
+        assert_range(bounds.y, 0.0, 1.0);
+    }
+
+    #[test]
+    fn nice_bounds_of_nothing_is_unit() {
+        let bounds = nice_bounds(std::iter::empty(), 0.1);
+        assert_range(bounds.x, 0.0, 1.0);
+        assert_range(bounds.y, 0.0, 1.0);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{
+    point2, AxisRange, AxisType, GeometryAxes, Line, Point2, Series, SeriesStats,
+};
+use gpui::{ContentMask, Hsla, PathBuilder};
+
+/// A line with a shaded band around it, e.g. a mean and its confidence interval.
+/// The band is drawn first, so the line stays on top
+pub struct BandSeries<X: AxisType> {
+    pub line: Line<X, f64>,
+    /// Lower and upper edge of the band at each point of the line
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+    pub band_color: Hsla,
+}
+impl<X: AxisType> BandSeries<X> {
+    pub fn new(line: Line<X, f64>) -> Self {
+        let band_color = line.color.opacity(0.2);
+        Self {
+            line,
+            lower: vec![],
+            upper: vec![],
+            band_color,
+        }
+    }
+    /// Shade between `lower` and `upper`, one value of each per point of the line.
+    /// The band is left out if their lengths don't match the line
+    pub fn with_band(mut self, lower: Vec<f64>, upper: Vec<f64>) -> Self {
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+    pub fn band_color(mut self, color: Hsla) -> Self {
+        self.band_color = color;
+        self
+    }
+    /// The outline of the band, along the upper edge and back along the lower one.
+    /// `None` if the band doesn't match the line or has no width anywhere
+    fn band_outline(&self) -> Option<Vec<Point2<X, f64>>> {
+        let points = self.line.points();
+        if points.len() < 2 || self.lower.len() != points.len() || self.upper.len() != points.len()
+        {
+            return None;
+        }
+        if self
+            .lower
+            .iter()
+            .zip(self.upper.iter())
+            .all(|(lower, upper)| lower == upper)
+        {
+            return None;
+        }
+        let upper = points
+            .iter()
+            .zip(self.upper.iter())
+            .map(|(point, upper)| point2(point.x, *upper));
+        let lower = points
+            .iter()
+            .zip(self.lower.iter())
+            .rev()
+            .map(|(point, lower)| point2(point.x, *lower));
+        Some(upper.chain(lower).collect())
+    }
+}
+impl<X: AxisType> GeometryAxes for BandSeries<X> {
+    type X = X;
+    type Y = f64;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        self.line.get_x_range()
+    }
+    /// Spans the band as well as the line
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        let line = self.line.get_y_range()?;
+        let values = self.lower.iter().chain(self.upper.iter()).copied();
+        let extent = values
+            .filter(|value| value.is_finite())
+            .fold((line.min(), line.max()), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        Some(AxisRange::new(extent.0, extent.1))
+    }
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.line.visible {
+            return;
+        }
+        if let Some(outline) = self.band_outline() {
+            let mut pixels = Vec::new();
+            cx.transform_points(&outline, &mut pixels);
+            let mut builder = PathBuilder::fill();
+            builder.move_to(pixels[0]);
+            for p in pixels[1..].iter() {
+                builder.line_to(*p);
+            }
+            builder.close();
+            let clip_rect = if self.line.clip { cx.clip_rect } else { None };
+            let color = cx.fade(self.band_color);
+            if let Ok(path) = builder.build() {
+                let (window, _cx) = cx.cx.as_mut().unwrap();
+                let mask = clip_rect.map(|bounds| ContentMask { bounds });
+                window.with_content_mask(mask, |window| window.paint_path(path, color));
+            }
+        }
+        self.line.render_axes(cx);
+    }
+    fn value_at_x(&self, x: Self::X) -> Option<Self::Y> {
+        self.line.value_at_x(x)
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        self.line.visit_points_in(x_range, f)
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.line.point_at(index)
+    }
+    fn visible_stats(&self, x_range: &AxisRange<Self::X>) -> Option<SeriesStats<Self::Y>> {
+        self.line.visible_stats(x_range)
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(&mut self.line)
+    }
+}
@@ -0,0 +1,184 @@
+use gpui::{Bounds, Hsla, Path, PathBuilder, Pixels, Window};
+
+/// Shapes merged into one path at most, bounding the overlap checks
+const MAX_BATCH_SHAPES: usize = 64;
+
+/// The fill color and optional (color, width) outline of the shapes in a batch
+type ShapeStyle = (Hsla, Option<(Hsla, Pixels)>);
+
+/// Where batches are painted to, the window outside of tests
+pub(crate) trait PaintSink {
+    fn paint_path(&mut self, path: Path<Pixels>, color: Hsla);
+}
+impl PaintSink for Window {
+    fn paint_path(&mut self, path: Path<Pixels>, color: Hsla) {
+        Window::paint_path(self, path, color);
+    }
+}
+
+/// Merges consecutive shapes of the same style into a single `paint_path`, as painting many
+/// small paths dominates dense plots. Shapes are only merged while they don't overlap, since
+/// overlapping sub-paths of one fill cancel out, which also keeps the painting order intact
+pub(crate) struct PaintBatcher {
+    style: Option<ShapeStyle>,
+    fill: Option<PathBuilder>,
+    edge: Option<PathBuilder>,
+    /// Bounds of the shapes in the current batch
+    shapes: Vec<Bounds<Pixels>>,
+}
+impl PaintBatcher {
+    pub fn new() -> Self {
+        Self {
+            style: None,
+            fill: None,
+            edge: None,
+            shapes: vec![],
+        }
+    }
+    /// Queue a shape covering `bounds`, traced into the path builders by `trace`.
+    /// Paints the current batch first if the shape can't join it
+    pub fn add_shape(
+        &mut self,
+        sink: &mut impl PaintSink,
+        bounds: Bounds<Pixels>,
+        style: ShapeStyle,
+        trace: impl Fn(&mut PathBuilder),
+    ) {
+        let overlaps = self.shapes.iter().any(|shape| shape.intersects(&bounds));
+        if self.style != Some(style) || overlaps || self.shapes.len() >= MAX_BATCH_SHAPES {
+            self.flush(sink);
+            self.style = Some(style);
+        }
+        trace(self.fill.get_or_insert_with(PathBuilder::fill));
+        if let Some((_, width)) = style.1 {
+            trace(self.edge.get_or_insert_with(|| PathBuilder::stroke(width)));
+        }
+        self.shapes.push(bounds);
+    }
+    /// Paint the queued shapes
+    pub fn flush(&mut self, sink: &mut impl PaintSink) {
+        let Some((color, edge)) = self.style.take() else {
+            return;
+        };
+        self.shapes.clear();
+        if let Some(Ok(path)) = self.fill.take().map(PathBuilder::build) {
+            sink.paint_path(path, color);
+        }
+        if let (Some(Ok(path)), Some((edge_color, _))) =
+            (self.edge.take().map(PathBuilder::build), edge)
+        {
+            sink.paint_path(path, edge_color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{point, px, size};
+
+    /// Counts the paints and records their colors
+    #[derive(Default)]
+    struct CountingSink {
+        colors: Vec<Hsla>,
+    }
+    impl PaintSink for CountingSink {
+        fn paint_path(&mut self, _path: Path<Pixels>, color: Hsla) {
+            self.colors.push(color);
+        }
+    }
+
+    fn square(x: f32, y: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(4.0), px(4.0)))
+    }
+    fn add(
+        batcher: &mut PaintBatcher,
+        sink: &mut CountingSink,
+        shape: Bounds<Pixels>,
+        style: ShapeStyle,
+    ) {
+        batcher.add_shape(sink, shape, style, |builder| {
+            builder.move_to(shape.origin);
+            builder.line_to(shape.top_right());
+            builder.line_to(shape.bottom_right());
+            builder.line_to(shape.bottom_left());
+            builder.close();
+        });
+    }
+
+    #[test]
+    fn merges_shapes_of_one_style() {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        for i in 0..10 {
+            add(
+                &mut batcher,
+                &mut sink,
+                square(i as f32 * 10.0, 0.0),
+                (gpui::red(), None),
+            );
+        }
+        assert!(sink.colors.is_empty());
+        batcher.flush(&mut sink);
+        assert_eq!(sink.colors, vec![gpui::red()]);
+    }
+
+    #[test]
+    fn paints_edges_after_their_fill() {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        let style = (gpui::red(), Some((gpui::blue(), px(1.0))));
+        for i in 0..10 {
+            add(&mut batcher, &mut sink, square(i as f32 * 10.0, 0.0), style);
+        }
+        batcher.flush(&mut sink);
+        assert_eq!(sink.colors, vec![gpui::red(), gpui::blue()]);
+    }
+
+    #[test]
+    fn splits_on_style_change_and_overlap() {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        add(
+            &mut batcher,
+            &mut sink,
+            square(0.0, 0.0),
+            (gpui::red(), None),
+        );
+        add(
+            &mut batcher,
+            &mut sink,
+            square(10.0, 0.0),
+            (gpui::blue(), None),
+        );
+        // overlaps the previous square, so it starts a batch of its own
+        add(
+            &mut batcher,
+            &mut sink,
+            square(12.0, 2.0),
+            (gpui::blue(), None),
+        );
+        batcher.flush(&mut sink);
+        assert_eq!(sink.colors, vec![gpui::red(), gpui::blue(), gpui::blue()]);
+    }
+
+    #[test]
+    fn bounds_the_batch_size() {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        for i in 0..MAX_BATCH_SHAPES + 1 {
+            add(
+                &mut batcher,
+                &mut sink,
+                square(i as f32 * 10.0, 0.0),
+                (gpui::red(), None),
+            );
+        }
+        batcher.flush(&mut sink);
+        assert_eq!(sink.colors.len(), 2);
+    }
+
+    #[test]
+    fn flushing_nothing_paints_nothing() {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        batcher.flush(&mut sink);
+        batcher.flush(&mut sink);
+        assert!(sink.colors.is_empty());
+    }
+}
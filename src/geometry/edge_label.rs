@@ -0,0 +1,84 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{
+    measure_text_in, point2, AxisType, GeometryAxes, Line, Text, TextBackground,
+};
+use gpui::{point, px, Bounds, Hsla, Pixels};
+use std::marker::PhantomData;
+
+/// A label pinned to the right edge of the plotting area at a data `y`, e.g. "$130 resistance".
+/// Follows `y` while panning and zooming but never moves away from the edge.
+/// It has no extent of its own, so it never constrains the view
+pub struct EdgeLabel<X: AxisType, Y: AxisType> {
+    pub y: Y,
+    pub text: String,
+    /// Font size. Follows the axes' label size when `None`
+    pub size: Option<Pixels>,
+    pub color: Hsla,
+    /// Length of the line joining the label to the edge
+    pub connector: Pixels,
+    pub visible: bool,
+    _phantom: PhantomData<X>,
+}
+impl<X: AxisType, Y: AxisType> EdgeLabel<X, Y> {
+    pub fn new(y: Y, text: impl Into<String>) -> Self {
+        Self {
+            y,
+            text: text.into(),
+            size: None,
+            color: gpui::black(),
+            connector: px(8.0),
+            visible: true,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = Some(size);
+        self
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn connector(mut self, connector: Pixels) -> Self {
+        self.connector = connector;
+        self
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for EdgeLabel<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible {
+            return;
+        }
+        let y_px = cx.axes_bounds.y.transform(cx.pixel_bounds.y, self.y);
+        if y_px < cx.pixel_bounds.min_y() || y_px > cx.pixel_bounds.max_y() {
+            return;
+        }
+        let edge = cx.pixel_bounds.max_x();
+        let size = self.size.unwrap_or(cx.label_font_size);
+        let family = cx.font_family.clone();
+        let color = cx.fade(self.color);
+        let background = TextBackground::new(cx.fade(gpui::white())).border(px(1.0), color);
+        let inset = background.padding + background.border.map_or(px(0.0), |(width, _)| width);
+        let (window, app) = cx.cx.as_mut().unwrap();
+        Line::between_points(point2(edge - self.connector, y_px), point2(edge, y_px))
+            .width(px(1.0))
+            .color(color)
+            .render(window, app, None);
+        let width = measure_text_in(window, &self.text, size, family.as_ref()).width;
+        let origin = point2(edge - self.connector - width, y_px - size / 2.0);
+        let mut text = Text::new(origin, size, self.text.clone())
+            .color(color)
+            .background(background);
+        text.font_family = family;
+        text.render(window, app, None);
+        cx.report_painted(Bounds::from_corners(
+            point(origin.x - inset, origin.y - inset),
+            point(
+                px(edge.0.max((origin.x + width + inset).0)),
+                y_px + size / 2.0 + inset,
+            ),
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::line::stroke_polyline;
+use crate::geometry::{point2, GeometryAxes, Point2, Series};
+use gpui::{px, Hsla, Pixels};
+
+/// The graph of `y = f(x)`, sampled anew across the visible x range on every render.
+/// It has no extent of its own, so it never constrains the view
+pub struct FunctionPlot {
+    pub f: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+    /// Number of points sampled across the visible range
+    pub samples: usize,
+    pub width: Pixels,
+    pub color: Hsla,
+    pub label: Option<String>,
+    pub visible: bool,
+}
+impl FunctionPlot {
+    pub fn new(f: impl Fn(f64) -> f64 + Send + Sync + 'static) -> Self {
+        Self {
+            f: Box::new(f),
+            samples: 500,
+            width: px(1.0),
+            color: gpui::black(),
+            label: None,
+            visible: true,
+        }
+    }
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = width;
+        self
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    /// The sampled curve, split wherever `f` isn't finite, e.g. at the poles of `tan`
+    fn segments(&self, min: f64, max: f64) -> Vec<Vec<Point2<f64, f64>>> {
+        let samples = self.samples.max(2);
+        let step = (max - min) / (samples - 1) as f64;
+        let mut segments = vec![];
+        let mut segment = vec![];
+        for i in 0..samples {
+            let x = min + step * i as f64;
+            let y = (self.f)(x);
+            if y.is_finite() {
+                segment.push(point2(x, y));
+            } else if !segment.is_empty() {
+                segments.push(std::mem::take(&mut segment));
+            }
+        }
+        if !segment.is_empty() {
+            segments.push(segment);
+        }
+        segments
+    }
+}
+impl GeometryAxes for FunctionPlot {
+    type X = f64;
+    type Y = f64;
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible {
+            return;
+        }
+        let x = cx.axes_bounds.x;
+        let segments = self.segments(x.min(), x.max());
+        let clip_rect = cx.clip_rect;
+        let mut pixels = Vec::new();
+        for segment in segments.iter().filter(|segment| segment.len() > 1) {
+            pixels.clear();
+            cx.transform_points(segment, &mut pixels);
+            let width = px(self.width.0.max(cx.min_line_width.0));
+            let color = cx.fade(self.color);
+            let (window, _app) = cx.cx.as_mut().unwrap();
+            stroke_polyline(window, pixels.iter().copied(), width, color, clip_rect);
+        }
+    }
+    fn value_at_x(&self, x: Self::X) -> Option<Self::Y> {
+        let y = (self.f)(x);
+        y.is_finite().then_some(y)
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(self)
+    }
+}
+impl Series for FunctionPlot {
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    fn color(&self) -> Hsla {
+        self.color
+    }
+    fn set_color(&mut self, color: Hsla) {
+        self.color = color;
+    }
+    fn visible(&self) -> bool {
+        self.visible
+    }
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+}
@@ -0,0 +1,75 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{AxisRange, AxisType, GeometryAxes, Point2, Series};
+
+/// Several elements that are plotted, ranged and removed as one
+pub struct Group<X: AxisType, Y: AxisType> {
+    pub elements: Vec<Box<dyn GeometryAxes<X = X, Y = Y>>>,
+    pub visible: bool,
+}
+impl<X: AxisType, Y: AxisType> Default for Group<X, Y> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<X: AxisType, Y: AxisType> Group<X, Y> {
+    pub fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+            visible: true,
+        }
+    }
+    pub fn add_element(&mut self, element: Box<dyn GeometryAxes<X = X, Y = Y>>) {
+        self.elements.push(element);
+    }
+    pub fn plot(&mut self, element: impl GeometryAxes<X = X, Y = Y> + 'static) {
+        self.elements.push(Box::new(element));
+    }
+    pub fn with(mut self, element: impl GeometryAxes<X = X, Y = Y> + 'static) -> Self {
+        self.plot(element);
+        self
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for Group<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        self.elements
+            .iter()
+            .filter_map(|element| element.get_x_range())
+            .reduce(|a, b| a.union(&b).unwrap_or(a))
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        self.elements
+            .iter()
+            .filter_map(|element| element.get_y_range())
+            .reduce(|a, b| a.union(&b).unwrap_or(a))
+    }
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible {
+            return;
+        }
+        for element in self.elements.iter_mut() {
+            element.render_axes(cx);
+        }
+    }
+    /// The value of the first element that has one at `x`
+    fn value_at_x(&self, x: Self::X) -> Option<Self::Y> {
+        self.elements
+            .iter()
+            .find_map(|element| element.value_at_x(x))
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        for element in self.elements.iter() {
+            element.visit_points_in(x_range, f);
+        }
+    }
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series)) {
+        for element in self.elements.iter_mut() {
+            element.for_each_series(f);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{AxisType, GeometryAxes, Series};
+use gpui::{fill, point, px, Bounds, Hsla, Pixels};
+use std::marker::PhantomData;
+
+/// A shaded y range spanning the full width of the plotting area, e.g. to mark the
+/// "warning" zone of a dashboard. Add it first, or `send_to_back`, to keep it behind the data.
+/// It has no extent of its own, so it never constrains the view
+pub struct HBand<X: AxisType, Y: AxisType> {
+    pub y0: Y,
+    pub y1: Y,
+    pub color: Hsla,
+    pub label: Option<String>,
+    pub visible: bool,
+    _phantom: PhantomData<X>,
+}
+impl<X: AxisType, Y: AxisType> HBand<X, Y> {
+    pub fn new(y0: Y, y1: Y, color: Hsla) -> Self {
+        Self {
+            y0,
+            y1,
+            color,
+            label: None,
+            visible: true,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for HBand<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible {
+            return;
+        }
+        let a = cx.axes_bounds.y.transform(cx.pixel_bounds.y, self.y0);
+        let b = cx.axes_bounds.y.transform(cx.pixel_bounds.y, self.y1);
+        let band = Bounds::from_corners(
+            point(cx.pixel_bounds.min_x(), px(a.0.min(b.0))),
+            point(cx.pixel_bounds.max_x(), px(a.0.max(b.0))),
+        )
+        .intersect(&cx.pixel_bounds.into_bounds());
+        if band.size.height <= px(0.0) {
+            return;
+        }
+        let color = cx.fade(self.color);
+        let (window, _cx) = cx.cx.as_mut().unwrap();
+        window.paint_quad(fill(band, color));
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(self)
+    }
+}
+impl<X: AxisType, Y: AxisType> Series for HBand<X, Y> {
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    fn color(&self) -> Hsla {
+        self.color
+    }
+    fn set_color(&mut self, color: Hsla) {
+        self.color = color;
+    }
+    fn visible(&self) -> bool {
+        self.visible
+    }
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+}
@@ -0,0 +1,244 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{AxisRange, AxisType, GeometryAxes, Point2, Series};
+use gpui::{point, px, Bounds, ContentMask, Edges, Hsla, PathBuilder, Pixels, Point};
+use std::collections::HashMap;
+
+const SQRT_3: f32 = 1.732_050_8;
+
+/// Points binned into a hexagonal grid, each cell colored by how many points it holds.
+/// The grid lives in pixel space, so cells keep their size while zooming
+pub struct HexBin<X: AxisType, Y: AxisType> {
+    pub points: Vec<Point2<X, Y>>,
+    /// Distance from the center of a cell to its corners
+    pub radius: Pixels,
+    /// Color of the cells holding the fewest points
+    pub low_color: Hsla,
+    /// Color of the cells holding the most points
+    pub high_color: Hsla,
+    pub label: Option<String>,
+    pub visible: bool,
+}
+impl<X: AxisType, Y: AxisType> Default for HexBin<X, Y> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<X: AxisType, Y: AxisType> HexBin<X, Y> {
+    pub fn new() -> Self {
+        Self {
+            points: vec![],
+            radius: px(8.0),
+            low_color: gpui::blue().opacity(0.2),
+            high_color: gpui::blue(),
+            label: None,
+            visible: true,
+        }
+    }
+    pub fn radius(mut self, radius: Pixels) -> Self {
+        self.radius = radius;
+        self
+    }
+    /// Color cells from `low` for the emptiest to `high` for the fullest
+    pub fn colors(mut self, low: Hsla, high: Hsla) -> Self {
+        self.low_color = low;
+        self.high_color = high;
+        self
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    pub fn add_point(&mut self, point: Point2<X, Y>) {
+        self.points.push(point);
+    }
+    pub fn add_points(&mut self, points: impl IntoIterator<Item = Point2<X, Y>>) {
+        self.points.extend(points);
+    }
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+}
+/// The axial coordinates of the pointy-top cell of `radius` containing `p`
+fn hex_cell(p: Point<Pixels>, origin: Point<Pixels>, radius: f32) -> (i32, i32) {
+    let x = (p.x - origin.x).0;
+    let y = (p.y - origin.y).0;
+    let q = (SQRT_3 / 3.0 * x - y / 3.0) / radius;
+    let r = (2.0 / 3.0 * y) / radius;
+    // round in cube coordinates, fixing up the component that moved the most
+    let s = -q - r;
+    let (mut round_q, mut round_r, round_s) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = (
+        (round_q - q).abs(),
+        (round_r - r).abs(),
+        (round_s - s).abs(),
+    );
+    if dq > dr && dq > ds {
+        round_q = -round_r - round_s;
+    } else if dr > ds {
+        round_r = -round_q - round_s;
+    }
+    (round_q as i32, round_r as i32)
+}
+fn hex_center((q, r): (i32, i32), origin: Point<Pixels>, radius: f32) -> Point<Pixels> {
+    point(
+        origin.x + px(radius * SQRT_3 * (q as f32 + r as f32 / 2.0)),
+        origin.y + px(radius * 1.5 * r as f32),
+    )
+}
+fn lerp_color(low: Hsla, high: Hsla, t: f32) -> Hsla {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Hsla {
+        h: lerp(low.h, high.h),
+        s: lerp(low.s, high.s),
+        l: lerp(low.l, high.l),
+        a: lerp(low.a, high.a),
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for HexBin<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        let first = self.points.first()?;
+        let (mut min, mut max) = (first.x, first.x);
+        for point in self.points.iter() {
+            if point.x < min {
+                min = point.x;
+            }
+            if point.x > max {
+                max = point.x;
+            }
+        }
+        Some(AxisRange::new(min, max))
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        let first = self.points.first()?;
+        let (mut min, mut max) = (first.y, first.y);
+        for point in self.points.iter() {
+            if point.y < min {
+                min = point.y;
+            }
+            if point.y > max {
+                max = point.y;
+            }
+        }
+        Some(AxisRange::new(min, max))
+    }
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        let radius = self.radius.0;
+        if !self.visible || self.points.is_empty() || radius <= 0.0 {
+            return;
+        }
+        let area = cx.data_clip;
+        let origin = area.origin;
+        let mut pixels = Vec::new();
+        cx.transform_points(&self.points, &mut pixels);
+        let mut counts: HashMap<(i32, i32), usize> = HashMap::new();
+        for p in pixels.into_iter() {
+            if p.x < area.left() - self.radius
+                || p.x > area.right() + self.radius
+                || p.y < area.top() - self.radius
+                || p.y > area.bottom() + self.radius
+            {
+                continue;
+            }
+            *counts.entry(hex_cell(p, origin, radius)).or_default() += 1;
+        }
+        let Some(max_count) = counts.values().copied().max() else {
+            return;
+        };
+        let clip_rect = cx.clip_rect;
+        let (low_color, high_color) = (cx.fade(self.low_color), cx.fade(self.high_color));
+        let mut painted: Option<Bounds<Pixels>> = None;
+        // cells just outside the clip still reach into it, the mask trims them at the frame
+        let cull = clip_rect.map(|clip| clip.extend(Edges::all(self.radius)));
+        let mask = clip_rect.map(|bounds| ContentMask { bounds });
+        let (window, _cx) = cx.cx.as_mut().unwrap();
+        window.with_content_mask(mask, |window| {
+            for (cell, count) in counts.into_iter() {
+                let center = hex_center(cell, origin, radius);
+                if let Some(cull) = cull {
+                    if !cull.contains(&center) {
+                        continue;
+                    }
+                }
+                let mut builder = PathBuilder::fill();
+                for i in 0..6 {
+                    let angle = (60.0 * i as f32 - 30.0).to_radians();
+                    let corner = point(
+                        center.x + px(radius * angle.cos()),
+                        center.y + px(radius * angle.sin()),
+                    );
+                    if i == 0 {
+                        builder.move_to(corner);
+                    } else {
+                        builder.line_to(corner);
+                    }
+                }
+                builder.close();
+                let t = if max_count > 1 {
+                    (count - 1) as f32 / (max_count - 1) as f32
+                } else {
+                    1.0
+                };
+                if let Ok(path) = builder.build() {
+                    window.paint_path(path, lerp_color(low_color, high_color, t));
+                }
+                let cell_bounds = Bounds::from_corners(
+                    point(center.x - self.radius, center.y - self.radius),
+                    point(center.x + self.radius, center.y + self.radius),
+                );
+                painted = Some(match painted {
+                    None => cell_bounds,
+                    Some(bounds) => bounds.union(&cell_bounds),
+                });
+            }
+        });
+        let painted = match (painted, clip_rect) {
+            (Some(bounds), Some(clip)) => Some(bounds.intersect(&clip)),
+            (painted, _) => painted,
+        };
+        if let Some(bounds) = painted {
+            cx.report_painted(bounds);
+        }
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        for point in self.points.iter() {
+            if x_range.contains(point.x) {
+                f(*point);
+            }
+        }
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.points.get(index).copied()
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(self)
+    }
+}
+impl<X: AxisType, Y: AxisType> Series for HexBin<X, Y> {
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    /// The color of the fullest cells
+    fn color(&self) -> Hsla {
+        self.high_color
+    }
+    /// Recolor the fullest cells, fading the emptiest ones to a translucent version of it
+    fn set_color(&mut self, color: Hsla) {
+        self.high_color = color;
+        self.low_color = color.opacity(0.2);
+    }
+    fn visible(&self) -> bool {
+        self.visible
+    }
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+}
@@ -0,0 +1,214 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{point2, AxisRange, GeometryAxes, Series};
+use gpui::{fill, point, px, Bounds, ContentMask, Hsla};
+
+/// How `Histogram` splits the sample range into bins
+#[derive(Clone, Copy, Debug)]
+pub enum HistogramBins {
+    /// This many bins of equal width across the samples
+    Count(usize),
+    /// Bins of this width, aligned to multiples of it
+    Width(f64),
+}
+
+/// Which way the bars of a `Histogram` grow
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// Samples along x, counts along y
+    #[default]
+    Vertical,
+    /// Samples along y, counts along x
+    Horizontal,
+}
+
+/// Bars counting how many of the raw `samples` fall into each bin
+pub struct Histogram {
+    samples: Vec<f64>,
+    bins: HistogramBins,
+    pub orientation: Orientation,
+    pub color: Hsla,
+    pub label: Option<String>,
+    pub visible: bool,
+    /// Start, end and count of each bin, computed on demand and dropped when the samples change
+    counts: Option<Vec<(f64, f64, usize)>>,
+}
+impl Histogram {
+    pub fn new(samples: Vec<f64>) -> Self {
+        let mut this = Self {
+            samples: vec![],
+            bins: HistogramBins::Count(10),
+            orientation: Orientation::Vertical,
+            color: gpui::blue().opacity(0.6),
+            label: None,
+            visible: true,
+            counts: None,
+        };
+        this.set_samples(samples);
+        this
+    }
+    pub fn bins(mut self, bins: HistogramBins) -> Self {
+        self.bins = bins;
+        self.counts = None;
+        self
+    }
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+    /// Replace the samples. Non-finite ones are dropped
+    pub fn set_samples(&mut self, samples: Vec<f64>) {
+        self.samples = samples;
+        self.samples.retain(|sample| sample.is_finite());
+        self.counts = None;
+    }
+    pub fn add_sample(&mut self, sample: f64) {
+        if sample.is_finite() {
+            self.samples.push(sample);
+            self.counts = None;
+        }
+    }
+    fn bin_edges(&self) -> Option<(f64, f64, usize)> {
+        let first = *self.samples.first()?;
+        let (min, max) = self
+            .samples
+            .iter()
+            .fold((first, first), |(min, max), s| (min.min(*s), max.max(*s)));
+        match self.bins {
+            HistogramBins::Count(count) => {
+                let count = count.max(1);
+                // a single value still gets a bar of some width
+                let width = if max > min {
+                    (max - min) / count as f64
+                } else {
+                    1.0
+                };
+                Some((min, width, count))
+            }
+            HistogramBins::Width(width) => {
+                if !width.is_finite() || width <= 0.0 {
+                    return None;
+                }
+                let start = (min / width).floor() * width;
+                let count = (((max - start) / width).floor() as usize + 1).max(1);
+                Some((start, width, count))
+            }
+        }
+    }
+    fn compute_counts(&self) -> Vec<(f64, f64, usize)> {
+        let Some((start, width, count)) = self.bin_edges() else {
+            return vec![];
+        };
+        let mut bins = vec![0; count];
+        for sample in self.samples.iter() {
+            let index = ((sample - start) / width).floor() as usize;
+            // the maximum lands on the far edge of the last bin
+            bins[index.min(count - 1)] += 1;
+        }
+        bins.into_iter()
+            .enumerate()
+            .map(|(i, n)| (start + width * i as f64, start + width * (i + 1) as f64, n))
+            .collect()
+    }
+    /// Start, end and count of each bin, recomputed if the samples or bins changed
+    pub fn counts(&mut self) -> &[(f64, f64, usize)] {
+        if self.counts.is_none() {
+            self.counts = Some(self.compute_counts());
+        }
+        self.counts.as_deref().unwrap_or_default()
+    }
+    fn sample_range(&self) -> Option<AxisRange<f64>> {
+        let (start, width, count) = self.bin_edges()?;
+        Some(AxisRange::new(start, start + width * count as f64))
+    }
+    fn count_range(&self) -> Option<AxisRange<f64>> {
+        self.bin_edges()?;
+        let max = match &self.counts {
+            Some(counts) => counts.iter().map(|(_, _, n)| *n).max(),
+            None => self.compute_counts().iter().map(|(_, _, n)| *n).max(),
+        };
+        Some(AxisRange::new(0.0, max.unwrap_or(0) as f64))
+    }
+}
+impl GeometryAxes for Histogram {
+    type X = f64;
+    type Y = f64;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        match self.orientation {
+            Orientation::Vertical => self.sample_range(),
+            Orientation::Horizontal => self.count_range(),
+        }
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        match self.orientation {
+            Orientation::Vertical => self.count_range(),
+            Orientation::Horizontal => self.sample_range(),
+        }
+    }
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible {
+            return;
+        }
+        let orientation = self.orientation;
+        let mut bars = vec![];
+        for (start, end, count) in self.counts().iter().copied() {
+            if count == 0 {
+                continue;
+            }
+            let (a, b) = match orientation {
+                Orientation::Vertical => (point2(start, 0.0), point2(end, count as f64)),
+                Orientation::Horizontal => (point2(0.0, start), point2(count as f64, end)),
+            };
+            let (a, b) = (cx.transform_point(a), cx.transform_point(b));
+            bars.push(Bounds::from_corners(
+                point(px(a.x.0.min(b.x.0)), px(a.y.0.min(b.y.0))),
+                point(px(a.x.0.max(b.x.0)), px(a.y.0.max(b.y.0))),
+            ));
+        }
+        let Some(painted) = bars.iter().copied().reduce(|a, b| a.union(&b)) else {
+            return;
+        };
+        let color = cx.fade(self.color);
+        let mask = cx.clip_rect.map(|bounds| ContentMask { bounds });
+        let (window, _cx) = cx.cx.as_mut().unwrap();
+        window.with_content_mask(mask, |window| {
+            for bar in bars {
+                window.paint_quad(fill(bar, color));
+            }
+        });
+        cx.report_painted(painted);
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(self)
+    }
+}
+impl Series for Histogram {
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    fn color(&self) -> Hsla {
+        self.color
+    }
+    fn set_color(&mut self, color: Hsla) {
+        self.color = color;
+    }
+    fn visible(&self) -> bool {
+        self.visible
+    }
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+}
@@ -0,0 +1,64 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{point2, AxesBounds, AxisRange, AxisType, GeometryAxes};
+use gpui::{point, px, Bounds, ContentMask, Corners, Pixels, RenderImage};
+use std::sync::Arc;
+use tracing::warn;
+
+/// An image stretched over a rectangle in data space, e.g. a map tile under a scatter.
+/// Moves and scales with the data while panning and zooming
+pub struct ImageLayer<X: AxisType, Y: AxisType> {
+    pub image: Arc<RenderImage>,
+    /// The data-space rectangle the image fills
+    pub placement: AxesBounds<X, Y>,
+    pub grayscale: bool,
+    pub visible: bool,
+}
+impl<X: AxisType, Y: AxisType> ImageLayer<X, Y> {
+    pub fn new(image: Arc<RenderImage>, placement: AxesBounds<X, Y>) -> Self {
+        Self {
+            image,
+            placement,
+            grayscale: false,
+            visible: true,
+        }
+    }
+    pub fn grayscale(mut self, grayscale: bool) -> Self {
+        self.grayscale = grayscale;
+        self
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for ImageLayer<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        Some(self.placement.x)
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        Some(self.placement.y)
+    }
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible || !cx.axes_bounds.intersects(&self.placement) {
+            return;
+        }
+        let a = cx.transform_point(point2(self.placement.x.min(), self.placement.y.min()));
+        let b = cx.transform_point(point2(self.placement.x.max(), self.placement.y.max()));
+        // y grows downward in pixels, and either axis may be reversed
+        let bounds = Bounds::from_corners(
+            point(px(a.x.0.min(b.x.0)), px(a.y.0.min(b.y.0))),
+            point(px(a.x.0.max(b.x.0)), px(a.y.0.max(b.y.0))),
+        );
+        let clip_rect = cx.clip_rect;
+        let grayscale = self.grayscale;
+        let image = self.image.clone();
+        let (window, _cx) = cx.cx.as_mut().unwrap();
+        let mask = clip_rect.map(|bounds| ContentMask { bounds });
+        let result = window.with_content_mask(mask, |window| {
+            window.paint_image(bounds, Corners::<Pixels>::default(), image, 0, grayscale)
+        });
+        if let Err(err) = result {
+            warn!("Failed to paint image layer: {}", err);
+            return;
+        }
+        cx.report_painted(bounds);
+    }
+}
@@ -1,13 +1,51 @@
 use crate::figure::axes::AxesContext;
-use crate::geometry::{AxisRange, AxisType, GeometryAxes, GeometryPixels, Point2};
-use gpui::{px, App, Bounds, Hsla, PathBuilder, Pixels, Window};
+use crate::geometry::batch::PaintBatcher;
+use crate::geometry::simplify::douglas_peucker_mask;
+use crate::geometry::{
+    AxesBounds, AxisRange, AxisType, GeometryAxes, GeometryPixels, MarkerSpec, Point2, Series,
+    SeriesStats,
+};
+use gpui::{point, px, App, Bounds, Hsla, PathBuilder, Pixels, Point, Window};
 use tracing::warn;
 
+/// Strokes thinner than this can vanish at some zoom levels
+pub const DEFAULT_MIN_LINE_WIDTH: Pixels = px(0.75);
+
+/// The order in which `Line::from_scatter` connects its points
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JoinOrder {
+    /// In the order they were given
+    #[default]
+    AsGiven,
+    /// By increasing x
+    SortedByX,
+    /// Starting from the first point, always on to the closest point not yet visited.
+    /// This is O(n²), meant for small sets like trajectories
+    NearestNeighbor,
+}
+
 #[derive(Clone, Debug)]
 pub struct Line<X: AxisType, Y: AxisType> {
-    pub points: Vec<Point2<X, Y>>,
+    points: Vec<Point2<X, Y>>,
     pub width: Pixels,
+    /// The stroke is never drawn thinner than this
+    pub min_width: Pixels,
     pub color: Hsla,
+    /// Whether to clip to `AxesContext::clip_rect` when rendered on axes
+    pub clip: bool,
+    pub label: Option<String>,
+    pub visible: bool,
+    /// Markers drawn at every point, in the same pass as the stroke
+    pub marker: Option<MarkerSpec>,
+    /// A single marker at the first point, e.g. to show where a trajectory starts
+    pub start_marker: Option<MarkerSpec>,
+    /// A single marker at the last point, e.g. the latest reading of a streaming line
+    pub end_marker: Option<MarkerSpec>,
+    /// Only draw the parts of the line inside this data rect, e.g. for a magnified region
+    pub data_clip: Option<AxesBounds<X, Y>>,
+    /// The (min, max) corners of `points`, kept up to date by every edit so the ranges are
+    /// O(1). `None` falls back to scanning the points
+    extent: Option<(Point2<X, Y>, Point2<X, Y>)>,
 }
 impl Default for Line<Pixels, Pixels> {
     fn default() -> Self {
@@ -19,7 +57,16 @@ impl<X: AxisType, Y: AxisType> Line<X, Y> {
         Self {
             points: vec![],
             width: 1.0.into(),
+            min_width: DEFAULT_MIN_LINE_WIDTH,
             color: gpui::black(),
+            clip: true,
+            label: None,
+            visible: true,
+            marker: None,
+            start_marker: None,
+            end_marker: None,
+            data_clip: None,
+            extent: None,
         }
     }
     pub fn between_points(start: Point2<X, Y>, end: Point2<X, Y>) -> Self {
@@ -28,84 +75,106 @@ impl<X: AxisType, Y: AxisType> Line<X, Y> {
         line.add_point(end);
         line
     }
+    /// Connect unordered `points` into a line, joined in `order`
+    pub fn from_scatter(mut points: Vec<Point2<X, Y>>, order: JoinOrder) -> Self {
+        match order {
+            JoinOrder::AsGiven => {}
+            JoinOrder::SortedByX => {
+                points.sort_by(|a, b| a.x.to_f64().total_cmp(&b.x.to_f64()));
+            }
+            JoinOrder::NearestNeighbor => points = nearest_neighbor_chain(points),
+        }
+        let mut line = Self::new();
+        for point in points {
+            line.add_point(point);
+        }
+        line
+    }
     pub fn width(mut self, width: Pixels) -> Self {
         self.width = width;
         self
     }
+    pub fn min_width(mut self, min_width: Pixels) -> Self {
+        self.min_width = min_width;
+        self
+    }
     pub fn color(mut self, color: Hsla) -> Self {
         self.color = color;
         self
     }
+    pub fn clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    pub fn marker(mut self, marker: MarkerSpec) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+    pub fn start_marker(mut self, marker: MarkerSpec) -> Self {
+        self.start_marker = Some(marker);
+        self
+    }
+    pub fn end_marker(mut self, marker: MarkerSpec) -> Self {
+        self.end_marker = Some(marker);
+        self
+    }
+    pub fn data_clip(mut self, data_clip: AxesBounds<X, Y>) -> Self {
+        self.data_clip = Some(data_clip);
+        self
+    }
     pub fn add_point(&mut self, point: Point2<X, Y>) {
+        if self.points.is_empty() {
+            self.extent = Some((point, point));
+        } else if let Some((min, max)) = self.extent.as_mut() {
+            if point.x < min.x {
+                min.x = point.x;
+            }
+            if point.y < min.y {
+                min.y = point.y;
+            }
+            if point.x > max.x {
+                max.x = point.x;
+            }
+            if point.y > max.y {
+                max.y = point.y;
+            }
+        }
         self.points.push(point);
     }
+    pub fn points(&self) -> &[Point2<X, Y>] {
+        &self.points
+    }
+    /// Replace all points, rescanning them for the ranges once
+    pub fn set_points(&mut self, points: Vec<Point2<X, Y>>) {
+        self.points = points;
+        self.recompute_extent();
+    }
     pub fn clear(&mut self) {
         self.points.clear();
+        self.extent = None;
     }
-}
-impl Line<Pixels, Pixels> {
-    pub fn render(
-        &mut self,
-        window: &mut Window,
-        _cx: &mut App,
-        pixel_bounds: Option<Bounds<Pixels>>,
-    ) {
-        match pixel_bounds {
-            Some(bounds) => {
-                let mut i = 0;
-                let mut line = Line::new().width(self.width).color(self.color);
-                while i < self.points.len() {
-                    while i < self.points.len() {
-                        let point = self.points[i];
-
-                        // Check if the point is within the bounds
-                        if !bounds.contains(&point.into()) {
-                            // break and draw the line
-                            i += 1;
-                            break;
-                        }
-
-                        line.add_point(point);
-                        i += 1;
-                    }
-                    if line.points.len() > 1 {
-                        line.render(window, _cx, None);
-                    }
-                    line.clear();
-                }
+    /// Rescan `points` for the ranges, after removing points
+    fn recompute_extent(&mut self) {
+        self.extent = match (self.scan_x_range(), self.scan_y_range()) {
+            (Some(x), Some(y)) => {
+                Some((Point2::new(x.min(), y.min()), Point2::new(x.max(), y.max())))
             }
-            None => {
-                if self.points.is_empty() {
-                    warn!("Line must have at least 1 points to render");
-                    return;
-                }
-
-                let mut builder = PathBuilder::stroke(px(self.width.0));
-                let Some(first_p) = self.points.first() else {
-                    return;
-                };
-
-                builder.move_to((*first_p).into());
-                for p in self.points.iter().skip(1) {
-                    builder.line_to((*p).into());
-                }
-
-                if let Ok(path) = builder.build() {
-                    window.paint_path(path, self.color);
-                }
-            }
-        }
+            _ => None,
+        };
     }
-}
-impl GeometryPixels for Line<Pixels, Pixels> {
-    fn render_pixels(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
-        self.render(window, cx, Some(bounds));
+    /// Drop the points that deviate less than `epsilon` from the line's shape,
+    /// see `simplify_douglas_peucker`
+    pub fn simplify(&mut self, epsilon: f64) {
+        let keep = douglas_peucker_mask(&self.points, epsilon);
+        let mut keep = keep.into_iter();
+        self.points.retain(|_| keep.next().unwrap_or(true));
+        self.recompute_extent();
     }
-}
-impl<X: AxisType, Y: AxisType> GeometryAxes for Line<X, Y> {
-    type X = X;
-    type Y = Y;
-    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+    fn scan_x_range(&self) -> Option<AxisRange<X>> {
         if self.points.is_empty() {
             return None;
         }
@@ -121,7 +190,7 @@ impl<X: AxisType, Y: AxisType> GeometryAxes for Line<X, Y> {
         }
         Some(AxisRange::new(min, max))
     }
-    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+    fn scan_y_range(&self) -> Option<AxisRange<Y>> {
         if self.points.is_empty() {
             return None;
         }
@@ -137,14 +206,435 @@ impl<X: AxisType, Y: AxisType> GeometryAxes for Line<X, Y> {
         }
         Some(AxisRange::new(min, max))
     }
+    /// Stroke `points` on the axes, with markers at them if `with_markers` is set
+    pub(crate) fn render_points(
+        &self,
+        points: &[Point2<X, Y>],
+        with_markers: bool,
+        cx: &mut AxesContext<X, Y>,
+    ) {
+        let mut pixels = Vec::new();
+        cx.transform_points(points, &mut pixels);
+        // as wide as painted, the stroke never thinner than `min_line_width`
+        let width = px(self.width.0.max(cx.min_line_width.0));
+        let extent = match self.marker.filter(|_| with_markers) {
+            Some(marker) => px(width.0.max(marker.size.0 * 2.0)),
+            None => width,
+        };
+        if let Some(bounds) = stroke_bounds(&pixels, extent) {
+            cx.report_painted(bounds);
+        }
+        let color = cx.fade(self.color);
+        let clip_rect = if self.clip { cx.clip_rect } else { None };
+        let opacity = cx.opacity;
+        let (window, _app) = cx.cx.as_mut().unwrap();
+        stroke_polyline(window, pixels.iter().copied(), width, color, clip_rect);
+        if !with_markers {
+            return;
+        }
+        if let Some(marker) = self.marker {
+            let mut batcher = PaintBatcher::new();
+            for point in pixels.iter().copied() {
+                marker
+                    .to_marker(point.into(), self.color)
+                    .fade(opacity)
+                    .render_batched(&mut batcher, window, clip_rect);
+            }
+            batcher.flush(window);
+        }
+    }
+    fn render_markers(&self, points: &[Point2<X, Y>], cx: &mut AxesContext<X, Y>) {
+        let Some(marker) = self.marker else {
+            return;
+        };
+        let mut pixels = Vec::new();
+        cx.transform_points(points, &mut pixels);
+        if let Some(bounds) = stroke_bounds(&pixels, marker.size * 2.0) {
+            cx.report_painted(bounds);
+        }
+        let clip_rect = if self.clip { cx.clip_rect } else { None };
+        let opacity = cx.opacity;
+        let (window, _app) = cx.cx.as_mut().unwrap();
+        let mut batcher = PaintBatcher::new();
+        for point in pixels.into_iter() {
+            marker
+                .to_marker(point.into(), self.color)
+                .fade(opacity)
+                .render_batched(&mut batcher, window, clip_rect);
+        }
+        batcher.flush(window);
+    }
+    /// Draw `start_marker` and `end_marker` at the ends of `points`, skipping an end
+    /// outside `data_clip`
+    pub(crate) fn render_end_markers(&self, points: &[Point2<X, Y>], cx: &mut AxesContext<X, Y>) {
+        let ends = [
+            (self.start_marker, points.first()),
+            (self.end_marker, points.last()),
+        ];
+        let clip_rect = if self.clip { cx.clip_rect } else { None };
+        for (marker, point) in ends {
+            let (Some(marker), Some(point)) = (marker, point) else {
+                continue;
+            };
+            if let Some(data_clip) = self.data_clip {
+                if !data_clip.x.contains(point.x) || !data_clip.y.contains(point.y) {
+                    continue;
+                }
+            }
+            let position = cx.transform_point(*point);
+            let opacity = cx.opacity;
+            let (window, _app) = cx.cx.as_mut().unwrap();
+            marker
+                .to_marker(position.into(), self.color)
+                .fade(opacity)
+                .render(window, clip_rect);
+        }
+    }
+}
+impl Line<Pixels, Pixels> {
+    pub fn render(
+        &mut self,
+        window: &mut Window,
+        _cx: &mut App,
+        pixel_bounds: Option<Bounds<Pixels>>,
+    ) {
+        if self.points.is_empty() {
+            warn!("Line must have at least 1 points to render");
+            return;
+        }
+        let points = self.points.iter().copied().map(Point::from);
+        let width = px(self.width.0.max(self.min_width.0));
+        stroke_polyline(window, points, width, self.color, pixel_bounds);
+    }
+}
+/// Stroke the polyline through `points` as a single path. With `pixel_bounds`, only the runs of
+/// points inside it are drawn, each as a sub-path
+pub(crate) fn stroke_polyline(
+    window: &mut Window,
+    mut points: impl Iterator<Item = Point<Pixels>>,
+    width: Pixels,
+    color: Hsla,
+    pixel_bounds: Option<Bounds<Pixels>>,
+) {
+    let mut builder = PathBuilder::stroke(width);
+    match pixel_bounds {
+        Some(bounds) => {
+            // every run of points inside the bounds is a sub-path of a single stroke
+            let mut previous: Option<Point<Pixels>> = None;
+            let mut in_run = false;
+            let mut painted = false;
+            for point in points {
+                if !bounds.contains(&point) {
+                    previous = None;
+                    in_run = false;
+                    continue;
+                }
+                match previous {
+                    Some(start) if !in_run => {
+                        builder.move_to(start);
+                        builder.line_to(point);
+                        in_run = true;
+                        painted = true;
+                    }
+                    Some(_) => {
+                        builder.line_to(point);
+                    }
+                    None => {}
+                }
+                previous = Some(point);
+            }
+            if !painted {
+                return;
+            }
+        }
+        None => {
+            let Some(first) = points.next() else {
+                return;
+            };
+            builder.move_to(first);
+            for point in points {
+                builder.line_to(point);
+            }
+        }
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+}
+impl GeometryPixels for Line<Pixels, Pixels> {
+    fn render_pixels(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.render(window, cx, Some(bounds));
+    }
+}
+/// `points` reordered by repeatedly moving on to the closest unvisited one
+fn nearest_neighbor_chain<X: AxisType, Y: AxisType>(
+    mut remaining: Vec<Point2<X, Y>>,
+) -> Vec<Point2<X, Y>> {
+    let mut chain = Vec::with_capacity(remaining.len());
+    if remaining.is_empty() {
+        return chain;
+    }
+    let mut current = remaining.remove(0);
+    chain.push(current);
+    while !remaining.is_empty() {
+        let distance = |p: &Point2<X, Y>| {
+            let dx = p.x.to_f64() - current.x.to_f64();
+            let dy = p.y.to_f64() - current.y.to_f64();
+            dx * dx + dy * dy
+        };
+        let (index, _) = remaining
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+            .unwrap();
+        current = remaining.swap_remove(index);
+        chain.push(current);
+    }
+    chain
+}
+/// The parts of the polyline `points` inside `bounds`, cutting segments where they cross its
+/// edges. Uses Liang-Barsky clipping on `to_f64` values
+fn clip_polyline<X: AxisType, Y: AxisType>(
+    points: &[Point2<X, Y>],
+    bounds: &AxesBounds<X, Y>,
+) -> Vec<Vec<Point2<X, Y>>> {
+    let (x0, x1) = (bounds.x.min().to_f64(), bounds.x.max().to_f64());
+    let (y0, y1) = (bounds.y.min().to_f64(), bounds.y.max().to_f64());
+    let (min_x, max_x) = (x0.min(x1), x0.max(x1));
+    let (min_y, max_y) = (y0.min(y1), y0.max(y1));
+    let mut pieces = vec![];
+    let mut piece: Vec<Point2<X, Y>> = vec![];
+    for pair in points.windows(2) {
+        let (ax, ay) = (pair[0].x.to_f64(), pair[0].y.to_f64());
+        let (dx, dy) = (pair[1].x.to_f64() - ax, pair[1].y.to_f64() - ay);
+        let (mut t0, mut t1) = (0.0f64, 1.0f64);
+        let mut inside = true;
+        for (p, q) in [
+            (-dx, ax - min_x),
+            (dx, max_x - ax),
+            (-dy, ay - min_y),
+            (dy, max_y - ay),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    inside = false;
+                    break;
+                }
+                continue;
+            }
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+            if t0 > t1 {
+                inside = false;
+                break;
+            }
+        }
+        if !inside {
+            if piece.len() > 1 {
+                pieces.push(std::mem::take(&mut piece));
+            }
+            piece.clear();
+            continue;
+        }
+        let at = |t: f64| Point2::new(X::from_f64(ax + dx * t), Y::from_f64(ay + dy * t));
+        if t0 > 0.0 || piece.is_empty() {
+            if piece.len() > 1 {
+                pieces.push(std::mem::take(&mut piece));
+            }
+            piece.clear();
+            piece.push(if t0 > 0.0 { at(t0) } else { pair[0] });
+        }
+        piece.push(if t1 < 1.0 { at(t1) } else { pair[1] });
+        if t1 < 1.0 {
+            pieces.push(std::mem::take(&mut piece));
+        }
+    }
+    if piece.len() > 1 {
+        pieces.push(piece);
+    }
+    pieces
+}
+/// The box covering a stroke of `width` through `points`
+fn stroke_bounds(points: &[Point<Pixels>], width: Pixels) -> Option<Bounds<Pixels>> {
+    let first = points.first()?;
+    let (mut min, mut max) = (*first, *first);
+    for p in points.iter() {
+        min = point(px(min.x.0.min(p.x.0)), px(min.y.0.min(p.y.0)));
+        max = point(px(max.x.0.max(p.x.0)), px(max.y.0.max(p.y.0)));
+    }
+    let half = width / 2.0;
+    Some(Bounds::from_corners(
+        point(min.x - half, min.y - half),
+        point(max.x + half, max.y + half),
+    ))
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for Line<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        match self.extent {
+            Some((min, max)) if !self.points.is_empty() => Some(AxisRange::new(min.x, max.x)),
+            _ => self.scan_x_range(),
+        }
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        match self.extent {
+            Some((min, max)) if !self.points.is_empty() => Some(AxisRange::new(min.y, max.y)),
+            _ => self.scan_y_range(),
+        }
+    }
     fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
-        let mut line = Line::new().width(self.width).color(self.color);
-        for point in self.points.iter().cloned() {
-            let point = cx.transform_point(point);
-            line.add_point(point.into());
-        }
-        let pixel_bounds = cx.pixel_bounds.into_bounds();
-        let (window, cx) = cx.cx.as_mut().unwrap();
-        line.render(window, cx, Some(pixel_bounds));
+        if !self.visible {
+            return;
+        }
+        // the cached extent keeps the culling O(1), without rescanning the points every frame
+        if let Some((min, max)) = self.extent.filter(|_| !self.points.is_empty()) {
+            let extent =
+                AxesBounds::new(AxisRange::new(min.x, max.x), AxisRange::new(min.y, max.y));
+            if !cx.axes_bounds.intersects(&extent) {
+                return;
+            }
+        }
+        let Some(data_clip) = self.data_clip else {
+            self.render_points(&self.points, true, cx);
+            self.render_end_markers(&self.points, cx);
+            return;
+        };
+        let pieces = clip_polyline(&self.points, &data_clip);
+        for piece in pieces.iter() {
+            self.render_points(piece, false, cx);
+        }
+        if self.marker.is_some() {
+            let inside: Vec<_> = self
+                .points
+                .iter()
+                .copied()
+                .filter(|p| data_clip.x.contains(p.x) && data_clip.y.contains(p.y))
+                .collect();
+            self.render_markers(&inside, cx);
+        }
+        self.render_end_markers(&self.points, cx);
+    }
+    /// Linearly interpolate between the points bracketing `x`
+    fn value_at_x(&self, x: Self::X) -> Option<Self::Y> {
+        if let [point] = self.points.as_slice() {
+            return (point.x == x).then_some(point.y);
+        }
+        for pair in self.points.windows(2) {
+            let (low, high) = if pair[0].x <= pair[1].x {
+                (pair[0], pair[1])
+            } else {
+                (pair[1], pair[0])
+            };
+            if x < low.x || x > high.x {
+                continue;
+            }
+            let span = (high.x - low.x).to_f64();
+            if span == 0.0 {
+                return Some(low.y);
+            }
+            let t = (x - low.x).to_f64() / span;
+            return Some(low.y + Y::Delta::from_f64((high.y - low.y).to_f64() * t));
+        }
+        None
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        for point in self.points.iter() {
+            if x_range.contains(point.x) {
+                f(*point);
+            }
+        }
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.points.get(index).copied()
+    }
+    fn visible_stats(&self, x_range: &AxisRange<Self::X>) -> Option<SeriesStats<Self::Y>> {
+        SeriesStats::from_visit(|f| self.visit_points_in(x_range, f))
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(self)
+    }
+    fn push_point(&mut self, point: Point2<Self::X, Self::Y>) -> bool {
+        self.add_point(point);
+        true
+    }
+}
+impl<X: AxisType, Y: AxisType> Series for Line<X, Y> {
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    fn color(&self) -> Hsla {
+        self.color
+    }
+    fn set_color(&mut self, color: Hsla) {
+        self.color = color;
+    }
+    fn visible(&self) -> bool {
+        self.visible
+    }
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::figure::axes::AxesModel;
+    use crate::figure::grid::GridModel;
+    use crate::geometry::point2;
+
+    fn line(points: &[(f64, f64)]) -> Line<f64, f64> {
+        let mut line = Line::new();
+        for &(x, y) in points {
+            line.add_point(point2(x, y));
+        }
+        line
+    }
+    fn ranges(line: &Line<f64, f64>) -> Option<((f64, f64), (f64, f64))> {
+        let (x, y) = (line.get_x_range()?, line.get_y_range()?);
+        Some(((x.min(), x.max()), (y.min(), y.max())))
+    }
+
+    #[test]
+    fn extent_follows_every_edit() {
+        let mut line = line(&[(1.0, 5.0), (-2.0, 3.0), (4.0, -1.0)]);
+        assert_eq!(ranges(&line), Some(((-2.0, 4.0), (-1.0, 5.0))));
+        line.set_points(vec![point2(10.0, 20.0), point2(11.0, 21.0)]);
+        assert_eq!(ranges(&line), Some(((10.0, 11.0), (20.0, 21.0))));
+        line.clear();
+        assert_eq!(ranges(&line), None);
+        line.add_point(point2(7.0, 8.0));
+        assert_eq!(ranges(&line), Some(((7.0, 7.0), (8.0, 8.0))));
+    }
+
+    #[test]
+    fn extent_shrinks_with_simplify() {
+        let mut line = line(&[(0.0, 0.0), (1.0, 0.1), (2.0, 0.0)]);
+        line.simplify(1.0);
+        assert_eq!(line.points().len(), 2);
+        assert_eq!(ranges(&line), Some(((0.0, 2.0), (0.0, 0.0))));
+    }
+
+    #[test]
+    fn culls_a_line_outside_the_view() {
+        let bounds = AxesBounds::new(AxisRange::new(0.0, 1.0), AxisRange::new(0.0, 1.0));
+        let model = AxesModel::new(bounds, GridModel::from_numbers(5, 5));
+        // without a window, anything that isn't culled would fail to paint
+        let mut cx = AxesContext::new_without_context(&model);
+        line(&[(5.0, 5.0), (6.0, 6.0)]).render_axes(&mut cx);
+        line(&[(0.5, -3.0), (0.5, -2.0)]).render_axes(&mut cx);
+        assert!(cx.painted_bounds.is_none());
     }
 }
@@ -1,6 +1,9 @@
 use crate::figure::axes::AxesContext;
-use crate::geometry::{AxisRange, AxisType, GeometryAxes, GeometryPixels, Point2};
-use gpui::{point, Hsla, Path, PathBuilder, Pixels};
+use crate::geometry::batch::{PaintBatcher, PaintSink};
+use crate::geometry::{
+    AxisRange, AxisType, GeometryAxes, GeometryPixels, GridIndex, Point2, Series, SeriesStats,
+};
+use gpui::{point, px, Bounds, Hsla, Path, PathBuilder, Pixels};
 
 #[derive(Debug, Clone, Copy)]
 pub enum MarkerShape {
@@ -9,11 +12,48 @@ pub enum MarkerShape {
     TriangleUp,
     TriangleDown,
 }
+/// How to draw markers that belong to another geometry, e.g. at the vertices of a line
+#[derive(Debug, Clone, Copy)]
+pub struct MarkerSpec {
+    pub shape: MarkerShape,
+    pub size: Pixels,
+    /// Defaults to the color of the owning geometry
+    pub color: Option<Hsla>,
+}
+impl MarkerSpec {
+    pub fn new(shape: MarkerShape, size: Pixels) -> Self {
+        Self {
+            shape,
+            size,
+            color: None,
+        }
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// A pixel-space marker at `position`, colored `default_color` unless overridden
+    pub fn to_marker(
+        &self,
+        position: Point2<Pixels, Pixels>,
+        default_color: Hsla,
+    ) -> Marker<Pixels, Pixels> {
+        Marker::new(position, self.size)
+            .shape(self.shape)
+            .color(self.color.unwrap_or(default_color))
+    }
+}
+
 pub struct Marker<X: AxisType, Y: AxisType> {
     pub position: Point2<X, Y>,
     pub size: Pixels,
     pub color: Hsla,
     pub shape: MarkerShape,
+    /// Outline drawn around the filled shape, if any
+    pub edge_color: Option<Hsla>,
+    pub edge_width: Pixels,
+    /// Whether to clip to `AxesContext::clip_rect` when rendered on axes
+    pub clip: bool,
 }
 impl<X: AxisType, Y: AxisType> Marker<X, Y> {
     pub fn new(position: Point2<X, Y>, size: Pixels) -> Self {
@@ -22,6 +62,9 @@ impl<X: AxisType, Y: AxisType> Marker<X, Y> {
             size,
             color: gpui::black(),
             shape: MarkerShape::Circle,
+            edge_color: None,
+            edge_width: px(1.0),
+            clip: true,
         }
     }
     pub fn shape(mut self, shape: MarkerShape) -> Self {
@@ -37,10 +80,47 @@ impl<X: AxisType, Y: AxisType> Marker<X, Y> {
         self.size = size;
         self
     }
+    /// Outline the marker with `color`
+    pub fn edge(mut self, color: Hsla, width: Pixels) -> Self {
+        self.edge_color = Some(color);
+        self.edge_width = width;
+        self
+    }
+    pub fn clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+}
+impl<X: AxisType, Y: AxisType> Marker<X, Y> {
+    /// The marker moved to its position on the axes, reporting the area it covers
+    fn to_pixels(&self, cx: &mut AxesContext<X, Y>) -> Marker<Pixels, Pixels> {
+        let position = cx.transform_point(self.position);
+        let mut marker = Marker::new(position.into(), self.size)
+            .color(self.color)
+            .shape(self.shape);
+        marker.edge_color = self.edge_color;
+        marker.edge_width = self.edge_width;
+        let marker = marker.fade(cx.opacity);
+        cx.report_painted(Bounds::from_corners(
+            point(position.x - self.size, position.y - self.size),
+            point(position.x + self.size, position.y + self.size),
+        ));
+        marker
+    }
 }
 impl Marker<Pixels, Pixels> {
-    fn get_path(&self) -> Path<Pixels> {
-        let mut builder = PathBuilder::fill();
+    /// The marker with the alpha of its fill and edge scaled by `opacity`
+    pub(crate) fn fade(mut self, opacity: f32) -> Self {
+        self.color = self.color.opacity(opacity);
+        self.edge_color = self.edge_color.map(|color| color.opacity(opacity));
+        self
+    }
+    fn get_path(&self, mut builder: PathBuilder) -> Path<Pixels> {
+        self.trace(&mut builder);
+        builder.build().unwrap()
+    }
+    /// Add the outline of the shape to `builder` as a closed sub-path
+    fn trace(&self, builder: &mut PathBuilder) {
         match self.shape {
             MarkerShape::Circle => {
                 for i in 0..16 {
@@ -100,7 +180,6 @@ impl Marker<Pixels, Pixels> {
                 builder.close();
             }
         }
-        builder.build().unwrap()
     }
     pub fn render(&self, window: &mut gpui::Window, pixel_bounds: Option<gpui::Bounds<Pixels>>) {
         if let Some(bounds) = pixel_bounds {
@@ -108,8 +187,37 @@ impl Marker<Pixels, Pixels> {
                 return;
             }
         }
-        let path = self.get_path();
+        let path = self.get_path(PathBuilder::fill());
         window.paint_path(path, self.color);
+        if let Some(edge_color) = self.edge_color {
+            let edge = self.get_path(PathBuilder::stroke(self.edge_width));
+            window.paint_path(edge, edge_color);
+        }
+    }
+    /// Like `render`, but merged with the neighboring markers of `batcher` into fewer paints
+    pub(crate) fn render_batched(
+        &self,
+        batcher: &mut PaintBatcher,
+        sink: &mut impl PaintSink,
+        pixel_bounds: Option<gpui::Bounds<Pixels>>,
+    ) {
+        if let Some(bounds) = pixel_bounds {
+            if !bounds.contains(&self.position.into()) {
+                return;
+            }
+        }
+        let edge = self.edge_color.map(|color| (color, self.edge_width));
+        let extent = match edge {
+            Some(_) => self.size + self.edge_width / 2.0,
+            None => self.size,
+        };
+        let bounds = Bounds::from_corners(
+            point(self.position.x - extent, self.position.y - extent),
+            point(self.position.x + extent, self.position.y + extent),
+        );
+        batcher.add_shape(sink, bounds, (self.color, edge), |builder| {
+            self.trace(builder)
+        });
     }
 }
 impl GeometryPixels for Marker<Pixels, Pixels> {
@@ -126,31 +234,167 @@ impl<X: AxisType, Y: AxisType> GeometryAxes for Marker<X, Y> {
     type X = X;
     type Y = Y;
     fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
-        let pixel_bounds = cx.pixel_bounds.into_bounds();
-        let position = cx.transform_point(self.position);
+        let clip_rect = if self.clip { cx.clip_rect } else { None };
+        let marker = self.to_pixels(cx);
+        let (window, _cx) = cx.cx.as_mut().unwrap();
 
-        let mut marker = Marker::new(position.into(), self.size)
-            .color(self.color)
-            .shape(self.shape);
-        let (window, cx) = cx.cx.as_mut().unwrap();
-
-        marker.render_pixels(pixel_bounds, window, cx);
+        marker.render(window, clip_rect);
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        if x_range.contains(self.position.x) {
+            f(self.position);
+        }
     }
 }
 
+/// Maps a per-point value to a marker size in pixels
+pub type MarkerSizeFn = Box<dyn Fn(f64) -> Pixels + Send + Sync>;
+
+/// Markers are culled through a spatial index from this many on
+const INDEX_MIN_MARKERS: usize = 10_000;
+
 pub struct Markers<X: AxisType, Y: AxisType> {
-    pub markers: Vec<Marker<X, Y>>,
+    markers: Vec<Marker<X, Y>>,
+    pub label: Option<String>,
+    pub visible: bool,
+    /// Per-marker values for bubble charts, mapped to pixels by `size_fn`
+    sizes: Vec<f64>,
+    size_fn: Option<MarkerSizeFn>,
+    /// Finds the markers in view without scanning all of them. Dropped by every change to
+    /// the markers or their sizes, and rebuilt on the next render with at least
+    /// `INDEX_MIN_MARKERS`
+    index: Option<GridIndex>,
+    /// How far the largest marker reaches past its position, widening the index queries
+    index_margin: Pixels,
 }
 impl<X: AxisType, Y: AxisType> Markers<X, Y> {
     pub fn new() -> Self {
-        Self { markers: vec![] }
+        Self {
+            markers: vec![],
+            label: None,
+            visible: true,
+            sizes: vec![],
+            size_fn: None,
+            index: None,
+            index_margin: px(0.0),
+        }
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
     }
     pub fn add_marker(&mut self, marker: Marker<X, Y>) {
         self.markers.push(marker);
+        self.index = None;
     }
 
     pub fn add_markers(&mut self, markers: Vec<Marker<X, Y>>) {
         self.markers.extend(markers);
+        self.index = None;
+    }
+    pub fn markers(&self) -> &[Marker<X, Y>] {
+        &self.markers
+    }
+    /// Edit the markers in place, e.g. to move them
+    pub fn markers_mut(&mut self) -> &mut Vec<Marker<X, Y>> {
+        self.index = None;
+        &mut self.markers
+    }
+    pub fn clear(&mut self) {
+        self.markers.clear();
+        self.index = None;
+    }
+    /// The per-marker values mapped to sizes, see `sizes`
+    pub fn size_values(&self) -> &[f64] {
+        &self.sizes
+    }
+    /// Replace the per-marker values, keeping the mapping set by `sizes`
+    pub fn set_size_values(&mut self, sizes: Vec<f64>) {
+        self.sizes = sizes;
+        self.index = None;
+    }
+    /// Size each marker from its value in `sizes` through `size_fn`.
+    /// Markers keep their own size if the lengths don't match.
+    pub fn sizes(
+        mut self,
+        sizes: Vec<f64>,
+        size_fn: impl Fn(f64) -> Pixels + Send + Sync + 'static,
+    ) -> Self {
+        self.sizes = sizes;
+        self.size_fn = Some(Box::new(size_fn));
+        self.index = None;
+        self
+    }
+    /// Size each marker proportional to its value, the largest being `max_size`
+    pub fn sizes_scaled(self, sizes: Vec<f64>, max_size: Pixels) -> Self {
+        let max = sizes
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold(0.0f64, |max, value| max.max(value.abs()));
+        self.sizes(sizes, move |value| {
+            if max > 0.0 && value.is_finite() {
+                max_size * (value.abs() / max) as f32
+            } else {
+                px(0.0)
+            }
+        })
+    }
+    fn marker_size(&self, index: usize) -> Option<Pixels> {
+        if self.sizes.len() != self.markers.len() {
+            return None;
+        }
+        let size_fn = self.size_fn.as_ref()?;
+        Some(size_fn(self.sizes[index]))
+    }
+    /// Index the markers by position, so rendering only visits the ones in view
+    fn rebuild_index(&mut self) {
+        let positions: Vec<_> = self
+            .markers
+            .iter()
+            .map(|marker| (marker.position.x.to_f64(), marker.position.y.to_f64()))
+            .collect();
+        self.index_margin = (0..self.markers.len())
+            .map(|i| {
+                let marker = &self.markers[i];
+                self.marker_size(i).unwrap_or(marker.size) + marker.edge_width
+            })
+            .fold(px(0.0), |max, extent| px(max.0.max(extent.0)));
+        self.index = Some(GridIndex::build(&positions));
+    }
+    /// The indices of the markers that may be in view, or `None` to visit all of them
+    fn indices_in_view(&mut self, cx: &AxesContext<X, Y>) -> Option<Vec<usize>> {
+        if self.markers.len() < INDEX_MIN_MARKERS {
+            return None;
+        }
+        if self.index.is_none() {
+            self.rebuild_index();
+        }
+        let index = self.index.as_ref()?;
+        // the whole axes, markers that aren't clipped may be drawn over the margins
+        let area = cx.full_bounds;
+        let margin = self.index_margin;
+        let (x, y) = (cx.axes_bounds.x, cx.axes_bounds.y);
+        let (x_px, y_px) = (cx.pixel_bounds.x, cx.pixel_bounds.y);
+        let x0 = x.transform_reverse(x_px, area.left() - margin).to_f64();
+        let x1 = x.transform_reverse(x_px, area.right() + margin).to_f64();
+        let y0 = y.transform_reverse(y_px, area.top() - margin).to_f64();
+        let y1 = y.transform_reverse(y_px, area.bottom() + margin).to_f64();
+        Some(index.query((x0, x1), (y0, y1)))
+    }
+    fn render_marker(&mut self, i: usize, batcher: &mut PaintBatcher, cx: &mut AxesContext<X, Y>) {
+        let size = self.marker_size(i);
+        let marker = &mut self.markers[i];
+        let clip_rect = if marker.clip { cx.clip_rect } else { None };
+        let default_size = marker.size;
+        marker.size = size.unwrap_or(default_size);
+        let pixel_marker = marker.to_pixels(cx);
+        marker.size = default_size;
+        let (window, _cx) = cx.cx.as_mut().unwrap();
+        pixel_marker.render_batched(batcher, window, clip_rect);
     }
 }
 impl<X: AxisType, Y: AxisType> GeometryAxes for Markers<X, Y> {
@@ -189,8 +433,140 @@ impl<X: AxisType, Y: AxisType> GeometryAxes for Markers<X, Y> {
         Some(AxisRange::new(min, max))
     }
     fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.visible {
+            return;
+        }
+        let mut batcher = PaintBatcher::new();
+        match self.indices_in_view(cx) {
+            Some(indices) => {
+                for i in indices {
+                    self.render_marker(i, &mut batcher, cx);
+                }
+            }
+            None => {
+                for i in 0..self.markers.len() {
+                    self.render_marker(i, &mut batcher, cx);
+                }
+            }
+        }
+        if let Some((window, _cx)) = cx.cx.as_mut() {
+            batcher.flush(window);
+        }
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        for marker in self.markers.iter() {
+            marker.visit_points_in(x_range, f);
+        }
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.markers.get(index).map(|marker| marker.position)
+    }
+    fn visible_stats(&self, x_range: &AxisRange<Self::X>) -> Option<SeriesStats<Self::Y>> {
+        SeriesStats::from_visit(|f| self.visit_points_in(x_range, f))
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(self)
+    }
+    /// Add a marker styled like the last one
+    fn push_point(&mut self, point: Point2<Self::X, Self::Y>) -> bool {
+        self.index = None;
+        let marker = match self.markers.last() {
+            Some(last) => {
+                let mut marker = Marker::new(point, last.size)
+                    .shape(last.shape)
+                    .color(last.color)
+                    .clip(last.clip);
+                marker.edge_color = last.edge_color;
+                marker.edge_width = last.edge_width;
+                marker
+            }
+            None => Marker::new(point, px(3.0)),
+        };
+        self.markers.push(marker);
+        true
+    }
+}
+impl<X: AxisType, Y: AxisType> Series for Markers<X, Y> {
+    fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    /// The color of the first marker, markers may be colored individually
+    fn color(&self) -> Hsla {
+        self.markers
+            .first()
+            .map(|marker| marker.color)
+            .unwrap_or_else(gpui::black)
+    }
+    /// Recolor every marker
+    fn set_color(&mut self, color: Hsla) {
         for marker in self.markers.iter_mut() {
-            marker.render_axes(cx);
+            marker.color = color;
         }
     }
+    fn visible(&self) -> bool {
+        self.visible
+    }
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::point2;
+
+    #[derive(Default)]
+    struct CountingSink {
+        paints: usize,
+    }
+    impl PaintSink for CountingSink {
+        fn paint_path(&mut self, _path: Path<Pixels>, _color: Hsla) {
+            self.paints += 1;
+        }
+    }
+
+    fn paint_row(count: usize, spacing: f32, edge: bool) -> usize {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        for i in 0..count {
+            let mut marker = Marker::new(point2(px(i as f32 * spacing), px(10.0)), px(3.0));
+            if edge {
+                marker = marker.edge(gpui::white(), px(1.0));
+            }
+            marker.render_batched(&mut batcher, &mut sink, None);
+        }
+        batcher.flush(&mut sink);
+        sink.paints
+    }
+
+    #[test]
+    fn batches_separate_markers_into_one_paint() {
+        assert_eq!(paint_row(50, 10.0, false), 1);
+        assert_eq!(paint_row(50, 10.0, true), 2);
+    }
+
+    #[test]
+    fn paints_overlapping_markers_one_by_one() {
+        assert_eq!(paint_row(50, 1.0, false), 50);
+    }
+
+    #[test]
+    fn skips_markers_outside_the_clip() {
+        let (mut batcher, mut sink) = (PaintBatcher::new(), CountingSink::default());
+        let clip = Bounds::from_corners(point(px(0.0), px(0.0)), point(px(5.0), px(5.0)));
+        Marker::new(point2(px(50.0), px(50.0)), px(3.0)).render_batched(
+            &mut batcher,
+            &mut sink,
+            Some(clip),
+        );
+        batcher.flush(&mut sink);
+        assert_eq!(sink.paints, 0);
+    }
 }
@@ -4,20 +4,49 @@ use gpui::{App, Bounds, Pixels, Window};
 use std::marker::PhantomData;
 
 mod axis;
+mod band;
+mod batch;
+mod edge_label;
+mod function;
+mod group;
+mod hband;
+mod hexbin;
+mod histogram;
+mod image;
 mod line;
 mod marker;
 mod point;
+mod range_hint;
+mod series;
+mod simplify;
 mod size;
+mod spatial_index;
+mod swap_chain;
 mod text;
+mod transformed;
 
 use crate::figure::axes::AxesContext;
 use crate::figure::SharedModel;
 pub use axis::*;
+pub use band::*;
+pub use edge_label::*;
+pub use function::*;
+pub use group::*;
+pub use hband::*;
+pub use hexbin::*;
+pub use histogram::*;
+pub use image::*;
 pub use line::*;
 pub use marker::*;
 pub use point::*;
+pub use range_hint::*;
+pub use series::*;
+pub use simplify::*;
 pub use size::*;
+pub use spatial_index::*;
+pub use swap_chain::*;
 pub use text::*;
+pub use transformed::*;
 
 /// Low-level Geometry
 pub trait GeometryPixels {
@@ -35,6 +64,39 @@ pub trait GeometryAxes: Send + Sync {
         None
     }
     fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>);
+    /// The value of the geometry at `x`, e.g. interpolated along a line
+    fn value_at_x(&self, _x: Self::X) -> Option<Self::Y> {
+        None
+    }
+    /// Call `f` with every point whose x lies within `x_range`
+    fn visit_points_in(
+        &self,
+        _x_range: &AxisRange<Self::X>,
+        _f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+    }
+    /// The point at `index`, for geometry made of indexed points
+    fn point_at(&self, _index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        None
+    }
+    /// Summary of the points whose x lies within `x_range`
+    fn visible_stats(&self, _x_range: &AxisRange<Self::X>) -> Option<SeriesStats<Self::Y>> {
+        None
+    }
+    /// This element as a series, if it is one
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        None
+    }
+    /// Call `f` with every series in this element, including nested ones
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series)) {
+        if let Some(series) = self.as_series_mut() {
+            f(series);
+        }
+    }
+    /// Append `point`, for editable geometry. Returns whether it was added
+    fn push_point(&mut self, _point: Point2<Self::X, Self::Y>) -> bool {
+        false
+    }
 }
 impl<T: GeometryAxes> GeometryAxes for SharedModel<T> {
     type X = T::X;
@@ -45,6 +107,28 @@ impl<T: GeometryAxes> GeometryAxes for SharedModel<T> {
     fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
         self.read().get_y_range()
     }
+    fn value_at_x(&self, x: Self::X) -> Option<Self::Y> {
+        self.read().value_at_x(x)
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        self.read().visit_points_in(x_range, f)
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.read().point_at(index)
+    }
+    fn visible_stats(&self, x_range: &AxisRange<Self::X>) -> Option<SeriesStats<Self::Y>> {
+        self.read().visible_stats(x_range)
+    }
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series)) {
+        self.write().for_each_series(f)
+    }
+    fn push_point(&mut self, point: Point2<Self::X, Self::Y>) -> bool {
+        self.write().push_point(point)
+    }
     fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
         self.write().render_axes(cx);
     }
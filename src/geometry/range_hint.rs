@@ -0,0 +1,39 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{AxisRange, AxisType, GeometryAxes, Point2};
+
+/// An element that is never drawn but is included when fitting the axes to the data
+#[derive(Clone, Copy, Debug)]
+pub struct RangeHint<X: AxisType, Y: AxisType> {
+    pub x: Option<AxisRange<X>>,
+    pub y: Option<AxisRange<Y>>,
+}
+impl<X: AxisType, Y: AxisType> RangeHint<X, Y> {
+    pub fn new(x: Option<AxisRange<X>>, y: Option<AxisRange<Y>>) -> Self {
+        Self { x, y }
+    }
+    /// Make sure the x axis spans `x`
+    pub fn include_x(x: X) -> Self {
+        Self::new(Some(AxisRange::new(x, x)), None)
+    }
+    /// Make sure the y axis spans `y`, e.g. `RangeHint::include_y(0.0)` to keep zero visible
+    pub fn include_y(y: Y) -> Self {
+        Self::new(None, Some(AxisRange::new(y, y)))
+    }
+    pub fn include_point(point: Point2<X, Y>) -> Self {
+        Self::new(
+            Some(AxisRange::new(point.x, point.x)),
+            Some(AxisRange::new(point.y, point.y)),
+        )
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for RangeHint<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        self.x
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        self.y
+    }
+    fn render_axes(&mut self, _cx: &mut AxesContext<Self::X, Self::Y>) {}
+}
@@ -0,0 +1,65 @@
+use crate::geometry::{AxisType, Point2};
+use gpui::Hsla;
+
+/// A data series on the axes.
+/// Legends, color cycling and visibility toggling work through this trait.
+/// Elements expose it with `GeometryAxes::as_series_mut`, which keeps it usable as `dyn Series`
+pub trait Series {
+    fn label(&self) -> Option<&str>;
+    fn set_label(&mut self, label: Option<String>);
+    fn color(&self) -> Hsla;
+    fn set_color(&mut self, color: Hsla);
+    fn visible(&self) -> bool;
+    fn set_visible(&mut self, visible: bool);
+}
+
+/// Summary of the y values of a series, see `GeometryAxes::visible_stats`
+#[derive(Clone, Copy, Debug)]
+pub struct SeriesStats<Y: AxisType> {
+    pub count: usize,
+    pub min: Y,
+    pub max: Y,
+    pub mean: f64,
+    /// The y of the last point visited
+    pub last: Y,
+}
+impl<Y: AxisType> SeriesStats<Y> {
+    /// Collect the stats of the points `visit` calls its argument with, `None` if there are none
+    pub fn from_visit<X: AxisType>(
+        visit: impl FnOnce(&mut dyn FnMut(Point2<X, Y>)),
+    ) -> Option<Self> {
+        let mut stats: Option<Self> = None;
+        let mut sum = 0.0;
+        visit(&mut |point| {
+            sum += point.y.to_f64();
+            stats = Some(match stats {
+                None => Self {
+                    count: 1,
+                    min: point.y,
+                    max: point.y,
+                    mean: 0.0,
+                    last: point.y,
+                },
+                Some(stats) => Self {
+                    count: stats.count + 1,
+                    min: if point.y < stats.min {
+                        point.y
+                    } else {
+                        stats.min
+                    },
+                    max: if point.y > stats.max {
+                        point.y
+                    } else {
+                        stats.max
+                    },
+                    mean: 0.0,
+                    last: point.y,
+                },
+            });
+        });
+        stats.map(|stats| Self {
+            mean: sum / stats.count as f64,
+            ..stats
+        })
+    }
+}
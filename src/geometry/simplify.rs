@@ -0,0 +1,119 @@
+use crate::geometry::{AxisType, Point2};
+
+/// Simplify a polyline with the Douglas-Peucker algorithm, keeping the vertices
+/// that deviate more than `epsilon` from the simplified shape
+pub fn simplify_douglas_peucker(
+    points: &[Point2<f64, f64>],
+    epsilon: f64,
+) -> Vec<Point2<f64, f64>> {
+    let keep = douglas_peucker_mask(points, epsilon);
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(*point))
+        .collect()
+}
+
+/// Which of `points` survive Douglas-Peucker simplification, measured in `to_f64` units.
+/// The first and last points are always kept
+pub(crate) fn douglas_peucker_mask<X: AxisType, Y: AxisType>(
+    points: &[Point2<X, Y>],
+    epsilon: f64,
+) -> Vec<bool> {
+    let mut keep = vec![false; points.len()];
+    if points.len() <= 2 {
+        keep.fill(true);
+        return keep;
+    }
+    let coords: Vec<(f64, f64)> = points
+        .iter()
+        .map(|p| (p.x.to_f64(), p.y.to_f64()))
+        .collect();
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    // explicit stack of (start, end) index pairs, avoiding deep recursion on long traces
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_distance = 0.0;
+        let mut max_index = start;
+        for i in start + 1..end {
+            let distance = perpendicular_distance(coords[i], coords[start], coords[end]);
+            if distance > max_distance {
+                max_distance = distance;
+                max_index = i;
+            }
+        }
+        if max_distance > epsilon {
+            keep[max_index] = true;
+            stack.push((start, max_index));
+            stack.push((max_index, end));
+        }
+    }
+    keep
+}
+
+/// Distance from `p` to the line through `a` and `b`
+fn perpendicular_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx.hypot(dy);
+    if length == 0.0 {
+        return (p.0 - a.0).hypot(p.1 - a.1);
+    }
+    (dy * p.0 - dx * p.1 + b.0 * a.1 - b.1 * a.0).abs() / length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::point2;
+
+    fn polyline(coords: &[(f64, f64)]) -> Vec<Point2<f64, f64>> {
+        coords.iter().map(|&(x, y)| point2(x, y)).collect()
+    }
+    fn kept(points: &[Point2<f64, f64>], epsilon: f64) -> Vec<usize> {
+        let keep = douglas_peucker_mask(points, epsilon);
+        (0..points.len()).filter(|&i| keep[i]).collect()
+    }
+
+    #[test]
+    fn keeps_the_corners_of_a_known_polyline() {
+        let points = polyline(&[
+            (0.0, 0.0),
+            (1.0, 0.1),
+            (2.0, -0.1),
+            (3.0, 5.0),
+            (4.0, 6.0),
+            (5.0, 7.0),
+            (6.0, 8.1),
+            (7.0, 9.0),
+            (8.0, 9.0),
+            (9.0, 9.0),
+        ]);
+        assert_eq!(kept(&points, 1.0), vec![0, 2, 3, 7, 9]);
+        assert_eq!(kept(&points, 0.05), vec![0, 1, 2, 3, 6, 7, 9]);
+        let simplified = simplify_douglas_peucker(&points, 1.0);
+        let expected: Vec<_> = [0, 2, 3, 7, 9].iter().map(|&i| points[i]).collect();
+        assert_eq!(simplified, expected);
+    }
+
+    #[test]
+    fn keeps_short_polylines_whole() {
+        assert!(simplify_douglas_peucker(&[], 1.0).is_empty());
+        let one = polyline(&[(1.0, 2.0)]);
+        assert_eq!(simplify_douglas_peucker(&one, 1.0), one);
+        let two = polyline(&[(1.0, 2.0), (3.0, 4.0)]);
+        assert_eq!(simplify_douglas_peucker(&two, 1.0), two);
+    }
+
+    #[test]
+    fn drops_collinear_points() {
+        let points = polyline(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+        assert_eq!(kept(&points, 0.0), vec![0, 3]);
+    }
+
+    #[test]
+    fn keeps_a_closed_loop_open() {
+        let points = polyline(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)]);
+        assert_eq!(kept(&points, 0.1), vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,170 @@
+/// Cells per axis at most, bounding the memory of sparse, widely spread data
+const MAX_CELLS_PER_AXIS: usize = 1024;
+/// Points per cell the grid aims for
+const POINTS_PER_CELL: usize = 4;
+
+/// Points bucketed into a uniform grid over their extent, to find the ones inside a rect
+/// without scanning all of them. Built once, rebuild it when the points change
+pub struct GridIndex {
+    min: (f64, f64),
+    max: (f64, f64),
+    /// Size of a cell in data units
+    cell: (f64, f64),
+    cols: usize,
+    rows: usize,
+    /// Where each cell's run starts in `indices`, followed by one past the last run
+    offsets: Vec<usize>,
+    /// Point indices, grouped by cell
+    indices: Vec<usize>,
+}
+impl GridIndex {
+    /// Index `points`, given as `to_f64` coordinates. Non-finite points are left out
+    pub fn build(points: &[(f64, f64)]) -> Self {
+        let finite = |p: &&(f64, f64)| p.0.is_finite() && p.1.is_finite();
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for p in points.iter().filter(finite) {
+            min = (min.0.min(p.0), min.1.min(p.1));
+            max = (max.0.max(p.0), max.1.max(p.1));
+        }
+        let per_axis = ((points.len() / POINTS_PER_CELL) as f64).sqrt().ceil() as usize;
+        let cols = per_axis.clamp(1, MAX_CELLS_PER_AXIS);
+        let rows = cols;
+        // a zero-span extent still gets a nonzero cell, keeping the division finite
+        let cell = (
+            ((max.0 - min.0) / cols as f64).max(f64::MIN_POSITIVE),
+            ((max.1 - min.1) / rows as f64).max(f64::MIN_POSITIVE),
+        );
+        let mut index = Self {
+            min,
+            max,
+            cell,
+            cols,
+            rows,
+            offsets: vec![0; cols * rows + 1],
+            indices: vec![],
+        };
+        // counting sort by cell, keeping the points of a cell in their original order
+        let cells: Vec<Option<usize>> = points
+            .iter()
+            .map(|p| finite(&p).then(|| index.cell_of(*p)))
+            .collect();
+        for cell in cells.iter().flatten() {
+            index.offsets[cell + 1] += 1;
+        }
+        let mut total = 0;
+        for offset in index.offsets.iter_mut() {
+            total += *offset;
+            *offset = total;
+        }
+        let mut next = index.offsets.clone();
+        index.indices = vec![0; index.offsets[cols * rows]];
+        for (i, cell) in cells.into_iter().enumerate() {
+            if let Some(cell) = cell {
+                index.indices[next[cell]] = i;
+                next[cell] += 1;
+            }
+        }
+        index
+    }
+    fn column(&self, x: f64) -> usize {
+        (((x - self.min.0) / self.cell.0).max(0.0) as usize).min(self.cols - 1)
+    }
+    fn row(&self, y: f64) -> usize {
+        (((y - self.min.1) / self.cell.1).max(0.0) as usize).min(self.rows - 1)
+    }
+    fn cell_of(&self, p: (f64, f64)) -> usize {
+        self.row(p.1) * self.cols + self.column(p.0)
+    }
+    /// Indices of the points in the cells overlapping `x` by `y`, in ascending order.
+    /// Points near the edges of the rect may lie outside it, callers still cull them
+    pub fn query(&self, x: (f64, f64), y: (f64, f64)) -> Vec<usize> {
+        let (x0, x1) = (x.0.min(x.1), x.0.max(x.1));
+        let (y0, y1) = (y.0.min(y.1), y.0.max(y.1));
+        let outside = x1 < self.min.0 || y1 < self.min.1 || x0 > self.max.0 || y0 > self.max.1;
+        if self.indices.is_empty() || outside {
+            return vec![];
+        }
+        let mut found = vec![];
+        for row in self.row(y0)..=self.row(y1) {
+            let start = row * self.cols;
+            let (first, last) = (start + self.column(x0), start + self.column(x1));
+            found.extend_from_slice(&self.indices[self.offsets[first]..self.offsets[last + 1]]);
+        }
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(n: usize) -> Vec<(f64, f64)> {
+        (0..n * n)
+            .map(|i| ((i % n) as f64, (i / n) as f64))
+            .collect()
+    }
+
+    #[test]
+    fn finds_points_in_rect() {
+        let points = grid(10);
+        let index = GridIndex::build(&points);
+        let found = index.query((2.5, 4.5), (6.5, 7.5));
+        for (i, p) in points.iter().enumerate() {
+            let inside = (2.5..=4.5).contains(&p.0) && (6.5..=7.5).contains(&p.1);
+            if inside {
+                assert!(found.contains(&i), "missing {:?}", p);
+            }
+        }
+        assert!(found.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn finds_points_on_max_edge() {
+        let points = grid(10);
+        let index = GridIndex::build(&points);
+        let corner = points.iter().position(|p| *p == (9.0, 9.0)).unwrap();
+        assert!(index.query((9.0, 9.0), (9.0, 9.0)).contains(&corner));
+        assert!(index.query((8.5, 9.5), (8.5, 9.5)).contains(&corner));
+    }
+
+    #[test]
+    fn leaves_out_non_finite_points() {
+        let points = vec![
+            (0.0, 0.0),
+            (f64::NAN, 1.0),
+            (1.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 0.0),
+            (1.0, 1.0),
+        ];
+        let index = GridIndex::build(&points);
+        assert_eq!(
+            index.query((f64::MIN, f64::MAX), (f64::MIN, f64::MAX)),
+            vec![0, 4]
+        );
+    }
+
+    #[test]
+    fn indexes_zero_span_extent() {
+        let points = vec![(5.0, 5.0); 3];
+        let index = GridIndex::build(&points);
+        assert_eq!(index.query((4.0, 6.0), (4.0, 6.0)), vec![0, 1, 2]);
+        assert_eq!(index.query((5.0, 5.0), (5.0, 5.0)), vec![0, 1, 2]);
+        assert!(index.query((6.0, 7.0), (4.0, 6.0)).is_empty());
+    }
+
+    #[test]
+    fn query_outside_extent_is_empty() {
+        let index = GridIndex::build(&grid(10));
+        assert!(index.query((10.5, 20.0), (0.0, 9.0)).is_empty());
+        assert!(index.query((0.0, 9.0), (10.5, 20.0)).is_empty());
+        assert!(index.query((-5.0, -0.5), (0.0, 9.0)).is_empty());
+        assert!(index.query((0.0, 9.0), (-5.0, -0.5)).is_empty());
+    }
+
+    #[test]
+    fn empty_index_finds_nothing() {
+        let index = GridIndex::build(&[]);
+        assert!(index.query((0.0, 1.0), (0.0, 1.0)).is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{AxisRange, AxisType, GeometryAxes, Line, Point2, Series, SeriesStats};
+use gpui::{Hsla, Pixels};
+use parking_lot::{Mutex, RwLock};
+use std::sync::Arc;
+
+struct Buffer<X, Y> {
+    points: Vec<Point2<X, Y>>,
+    /// The (min, max) corners of `points`, computed by the producer
+    extent: Option<(Point2<X, Y>, Point2<X, Y>)>,
+}
+impl<X, Y> Default for Buffer<X, Y> {
+    fn default() -> Self {
+        Self {
+            points: vec![],
+            extent: None,
+        }
+    }
+}
+struct SwapChain<X, Y> {
+    front: RwLock<Buffer<X, Y>>,
+    back: Mutex<Buffer<X, Y>>,
+}
+
+/// A line fed from another thread through a double buffer.
+/// The producer fills the back buffer with `update` and publishes it with `swap`,
+/// which only exchanges the buffers, so rendering never waits on a large copy.
+/// Clones share the buffers, hand one to the producer and plot another
+pub struct SwapChainSeries<X: AxisType, Y: AxisType> {
+    chain: Arc<SwapChain<X, Y>>,
+    /// Styling of the line, its points are unused
+    style: Line<X, Y>,
+}
+impl<X: AxisType, Y: AxisType> Clone for SwapChainSeries<X, Y> {
+    fn clone(&self) -> Self {
+        Self {
+            chain: self.chain.clone(),
+            style: self.style.clone(),
+        }
+    }
+}
+impl<X: AxisType, Y: AxisType> Default for SwapChainSeries<X, Y> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<X: AxisType, Y: AxisType> SwapChainSeries<X, Y> {
+    pub fn new() -> Self {
+        Self {
+            chain: Arc::new(SwapChain {
+                front: RwLock::new(Buffer::default()),
+                back: Mutex::new(Buffer::default()),
+            }),
+            style: Line::new(),
+        }
+    }
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.style.width = width;
+        self
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.style.color = color;
+        self
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.style.label = Some(label.into());
+        self
+    }
+    /// Replace the back buffer with `points`. Nothing shows until `swap`
+    pub fn update(&self, points: Vec<Point2<X, Y>>) {
+        let extent = points.first().map(|first| {
+            points
+                .iter()
+                .fold((*first, *first), |(mut min, mut max), p| {
+                    if p.x < min.x {
+                        min.x = p.x;
+                    }
+                    if p.y < min.y {
+                        min.y = p.y;
+                    }
+                    if p.x > max.x {
+                        max.x = p.x;
+                    }
+                    if p.y > max.y {
+                        max.y = p.y;
+                    }
+                    (min, max)
+                })
+        });
+        *self.chain.back.lock() = Buffer { points, extent };
+    }
+    /// Publish the back buffer, making the previous front the new back buffer
+    pub fn swap(&self) {
+        let mut back = self.chain.back.lock();
+        let mut front = self.chain.front.write();
+        std::mem::swap(&mut *front, &mut *back);
+    }
+}
+impl<X: AxisType, Y: AxisType> GeometryAxes for SwapChainSeries<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        let (min, max) = self.chain.front.read().extent?;
+        Some(AxisRange::new(min.x, max.x))
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        let (min, max) = self.chain.front.read().extent?;
+        Some(AxisRange::new(min.y, max.y))
+    }
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.style.visible {
+            return;
+        }
+        let front = self.chain.front.read();
+        self.style.render_points(&front.points, true, cx);
+        self.style.render_end_markers(&front.points, cx);
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        for point in self.chain.front.read().points.iter() {
+            if x_range.contains(point.x) {
+                f(*point);
+            }
+        }
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.chain.front.read().points.get(index).copied()
+    }
+    fn visible_stats(&self, x_range: &AxisRange<Self::X>) -> Option<SeriesStats<Self::Y>> {
+        SeriesStats::from_visit(|f| self.visit_points_in(x_range, f))
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        Some(&mut self.style)
+    }
+}
@@ -1,12 +1,121 @@
 use crate::geometry::{GeometryPixels, Point2};
-use gpui::{App, Bounds, Pixels, SharedString, Window};
+use gpui::{
+    fill, point, px, size, App, Bounds, Corners, Edges, Font, Hsla, Pixels, SharedString, Size,
+    TextRun, Window,
+};
+
+/// Typography of a figure's title and the text drawn on its axes, see `FigureModel::font`
+#[derive(Clone, Debug)]
+pub struct FontConfig {
+    /// Falls back to the window's font when `None` or not installed
+    pub family: Option<SharedString>,
+    pub title_size: Pixels,
+    /// Size of the labels drawn over the plotting area, like the data cursor's
+    pub label_size: Pixels,
+    pub tick_size: Pixels,
+}
+impl Default for FontConfig {
+    fn default() -> Self {
+        Self {
+            family: None,
+            title_size: px(16.0),
+            label_size: px(12.0),
+            tick_size: px(12.0),
+        }
+    }
+}
+impl FontConfig {
+    pub fn family(mut self, family: impl Into<SharedString>) -> Self {
+        self.family = Some(family.into());
+        self
+    }
+    pub fn title_size(mut self, size: Pixels) -> Self {
+        self.title_size = size;
+        self
+    }
+    pub fn label_size(mut self, size: Pixels) -> Self {
+        self.label_size = size;
+        self
+    }
+    pub fn tick_size(mut self, size: Pixels) -> Self {
+        self.tick_size = size;
+        self
+    }
+}
+
+/// A filled box drawn behind a text, keeping it readable over busy plots
+#[derive(Clone, Copy, Debug)]
+pub struct TextBackground {
+    pub color: Hsla,
+    /// Space between the text and the edge of the box
+    pub padding: Pixels,
+    pub corner_radius: Pixels,
+    /// Width and color of the border, if any
+    pub border: Option<(Pixels, Hsla)>,
+}
+impl TextBackground {
+    pub fn new(color: Hsla) -> Self {
+        Self {
+            color,
+            padding: px(2.0),
+            corner_radius: px(3.0),
+            border: None,
+        }
+    }
+    pub fn padding(mut self, padding: Pixels) -> Self {
+        self.padding = padding;
+        self
+    }
+    pub fn corner_radius(mut self, corner_radius: Pixels) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
+    pub fn border(mut self, width: Pixels, color: Hsla) -> Self {
+        self.border = Some((width, color));
+        self
+    }
+}
 
 pub struct Text {
     pub origin: Point2<Pixels, Pixels>,
     pub size: Pixels,
     pub text: String,
+    pub background: Option<TextBackground>,
+    /// Truncate with an ellipsis to fit within this width
+    pub max_width: Option<Pixels>,
+    /// Defaults to the window's text color
+    pub color: Option<Hsla>,
+    /// Defaults to the window's font, and falls back to it if this one isn't installed
+    pub font_family: Option<SharedString>,
 }
 impl Text {
+    pub fn new(origin: Point2<Pixels, Pixels>, size: Pixels, text: impl Into<String>) -> Self {
+        Self {
+            origin,
+            size,
+            text: text.into(),
+            background: None,
+            max_width: None,
+            color: None,
+            font_family: None,
+        }
+    }
+    pub fn background(mut self, background: TextBackground) -> Self {
+        self.background = Some(background);
+        self
+    }
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = Some(color);
+        self
+    }
+    pub fn max_width(mut self, max_width: Pixels) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+    pub fn font_family(mut self, font_family: impl Into<SharedString>) -> Self {
+        self.font_family = Some(font_family.into());
+        self
+    }
     pub fn render(
         &mut self,
         window: &mut Window,
@@ -18,19 +127,177 @@ impl Text {
                 return;
             }
         }
-        let shared_string = SharedString::from(self.text.clone());
+        let family = self.font_family.as_ref();
+        let text = match self.max_width {
+            Some(max_width) => {
+                truncate_to_width_in(window, &self.text, self.size, max_width, family)
+            }
+            None => self.text.clone(),
+        };
+        let runs = text_runs(window, text.len(), self.color, family);
+        let shared_string = SharedString::from(text);
         let shaped_line = window
             .text_system()
-            .shape_line(shared_string, self.size, &[])
+            .shape_line(shared_string, self.size, &runs)
             .unwrap();
+        if let Some(background) = self.background {
+            let padding = background.padding;
+            let bounds = Bounds::new(
+                point(self.origin.x - padding, self.origin.y - padding),
+                size(shaped_line.width + padding * 2.0, self.size + padding * 2.0),
+            );
+            let mut quad =
+                fill(bounds, background.color).corner_radii(Corners::all(background.corner_radius));
+            if let Some((width, color)) = background.border {
+                quad = quad.border_widths(Edges::all(width)).border_color(color);
+            }
+            window.paint_quad(quad);
+        }
         shaped_line
             .paint(self.origin.into(), self.size, window, cx)
             .unwrap();
     }
 }
 
+/// The runs to shape `len` bytes with, empty for the window's defaults
+fn text_runs(
+    window: &Window,
+    len: usize,
+    color: Option<Hsla>,
+    family: Option<&SharedString>,
+) -> Vec<TextRun> {
+    if color.is_none() && family.is_none() {
+        return vec![];
+    }
+    let mut run = window.text_style().to_run(len);
+    if let Some(color) = color {
+        run.color = color;
+    }
+    if let Some(family) = family {
+        let font = Font {
+            family: family.clone(),
+            ..run.font.clone()
+        };
+        // keep the window's font if the requested one can't be loaded
+        if window.text_system().font_id(&font).is_ok() {
+            run.font = font;
+        }
+    }
+    vec![run]
+}
+
+/// The extent of `text` once shaped at `size`. The height is `size`, the same as `Text` lays
+/// out its line and background
+pub fn measure_text(window: &Window, text: &str, size: Pixels) -> Size<Pixels> {
+    measure_text_in(window, text, size, None)
+}
+/// `measure_text` in the font `family`, see `Text::font_family`
+pub fn measure_text_in(
+    window: &Window,
+    text: &str,
+    size: Pixels,
+    family: Option<&SharedString>,
+) -> Size<Pixels> {
+    let runs = text_runs(window, text.len(), None, family);
+    let width = window
+        .text_system()
+        .shape_line(SharedString::from(text.to_string()), size, &runs)
+        .map(|line| line.width)
+        .unwrap_or(px(0.0));
+    gpui::size(width, size)
+}
+
+/// `text` shortened with a trailing "…" until it fits in `max_width` once shaped at `size`.
+/// Returned unchanged if it already fits
+pub fn truncate_to_width(window: &Window, text: &str, size: Pixels, max_width: Pixels) -> String {
+    truncate_to_width_in(window, text, size, max_width, None)
+}
+/// `truncate_to_width` in the font `family`, see `Text::font_family`
+pub fn truncate_to_width_in(
+    window: &Window,
+    text: &str,
+    size: Pixels,
+    max_width: Pixels,
+    family: Option<&SharedString>,
+) -> String {
+    truncate_with(text, max_width, |text| {
+        measure_text_in(window, text, size, family).width
+    })
+}
+/// `truncate_to_width_in` measuring with `width`. Binary searches the cut, as the width only
+/// grows with the number of characters kept, so it takes O(log n) measurements
+fn truncate_with(text: &str, max_width: Pixels, width: impl Fn(&str) -> Pixels) -> String {
+    if width(text) <= max_width {
+        return text.to_string();
+    }
+    // the byte offset after each number of characters kept
+    let cuts: Vec<usize> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let with_ellipsis = |kept: usize| format!("{}…", &text[..cuts[kept]]);
+    // the most characters that still fit before the ellipsis lie in `low..high`
+    let (mut low, mut high) = (0, cuts.len() - 1);
+    if width(&with_ellipsis(0)) > max_width {
+        return String::new();
+    }
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if width(&with_ellipsis(middle)) <= max_width {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    with_ellipsis(low)
+}
+
 impl GeometryPixels for Text {
     fn render_pixels(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
         self.render(window, cx, Some(bounds));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Truncate with every character 1px wide, returning the result and the measurements taken
+    fn truncate(text: &str, max_width: f32) -> (String, usize) {
+        let measured = Cell::new(0);
+        let truncated = truncate_with(text, px(max_width), |text| {
+            measured.set(measured.get() + 1);
+            px(text.chars().count() as f32)
+        });
+        (truncated, measured.get())
+    }
+
+    #[test]
+    fn keeps_text_that_fits() {
+        assert_eq!(truncate("hello", 5.0), ("hello".to_string(), 1));
+        assert_eq!(truncate("", 0.0).0, "");
+    }
+
+    #[test]
+    fn cuts_to_the_widest_prefix_that_fits() {
+        assert_eq!(truncate("hello world", 5.0).0, "hell…");
+        assert_eq!(truncate("hello world", 10.0).0, "hello wor…");
+        assert_eq!(truncate("hello world", 1.0).0, "…");
+        assert_eq!(truncate("hello world", 0.5).0, "");
+    }
+
+    #[test]
+    fn cuts_on_char_boundaries() {
+        assert_eq!(truncate("αβγδε", 3.0).0, "αβ…");
+    }
+
+    #[test]
+    fn measures_logarithmically() {
+        let text = "x".repeat(1000);
+        let (truncated, measured) = truncate(&text, 500.0);
+        assert_eq!(truncated.chars().count(), 500);
+        assert!(measured <= 13, "measured {} times", measured);
+    }
+}
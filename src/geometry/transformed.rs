@@ -0,0 +1,117 @@
+use crate::figure::axes::AxesContext;
+use crate::geometry::{point2, AxesBounds, AxisRange, AxisType, GeometryAxes, Point2, Series};
+
+/// A child geometry drawn scaled and shifted, e.g. to repeat a pattern at several offsets
+/// without copying its data. A point `p` of the child is drawn at `p * scale + offset`,
+/// in the units of `AxisType::to_f64`
+pub struct Transformed<G: GeometryAxes> {
+    pub child: G,
+    pub scale: (f64, f64),
+    pub offset: (f64, f64),
+}
+impl<G: GeometryAxes> Transformed<G> {
+    pub fn new(child: G) -> Self {
+        Self {
+            child,
+            scale: (1.0, 1.0),
+            offset: (0.0, 0.0),
+        }
+    }
+    pub fn scale(mut self, x: f64, y: f64) -> Self {
+        self.scale = (x, y);
+        self
+    }
+    pub fn offset(mut self, x: f64, y: f64) -> Self {
+        self.offset = (x, y);
+        self
+    }
+    fn forward(&self, point: Point2<G::X, G::Y>) -> Point2<G::X, G::Y> {
+        point2(
+            forward(point.x, self.scale.0, self.offset.0),
+            forward(point.y, self.scale.1, self.offset.1),
+        )
+    }
+    fn is_invertible(&self) -> bool {
+        self.scale.0 != 0.0 && self.scale.1 != 0.0
+    }
+}
+fn forward<T: AxisType>(value: T, scale: f64, offset: f64) -> T {
+    T::from_f64(value.to_f64() * scale + offset)
+}
+fn inverse<T: AxisType>(value: T, scale: f64, offset: f64) -> T {
+    T::from_f64((value.to_f64() - offset) / scale)
+}
+/// `range` with both ends mapped through `f`, keeping its orientation
+fn map_range<T: AxisType>(range: AxisRange<T>, f: impl Fn(T) -> T) -> AxisRange<T> {
+    AxisRange::new(f(range.min()), f(range.max()))
+}
+fn ascending<T: AxisType>(range: AxisRange<T>) -> AxisRange<T> {
+    if range.is_descending() {
+        range.reversed()
+    } else {
+        range
+    }
+}
+impl<G: GeometryAxes> GeometryAxes for Transformed<G> {
+    type X = G::X;
+    type Y = G::Y;
+    fn get_x_range(&self) -> Option<AxisRange<Self::X>> {
+        let (scale, offset) = (self.scale.0, self.offset.0);
+        let range = self.child.get_x_range()?;
+        Some(ascending(map_range(range, |x| forward(x, scale, offset))))
+    }
+    fn get_y_range(&self) -> Option<AxisRange<Self::Y>> {
+        let (scale, offset) = (self.scale.1, self.offset.1);
+        let range = self.child.get_y_range()?;
+        Some(ascending(map_range(range, |y| forward(y, scale, offset))))
+    }
+    /// Render the child against the visible bounds mapped back into its own space and a scale
+    /// adjusted to match, which lands every child point where its transformed self would be
+    fn render_axes(&mut self, cx: &mut AxesContext<Self::X, Self::Y>) {
+        if !self.is_invertible() {
+            return;
+        }
+        let (scale, offset) = (self.scale, self.offset);
+        let (axes_bounds, pixel_bounds) = (cx.axes_bounds, cx.pixel_bounds);
+        cx.axes_bounds = AxesBounds::new(
+            map_range(axes_bounds.x, |x| inverse(x, scale.0, offset.0)),
+            map_range(axes_bounds.y, |y| inverse(y, scale.1, offset.1)),
+        );
+        cx.pixel_bounds.x.pixels_per_element *= scale.0;
+        cx.pixel_bounds.y.pixels_per_element *= scale.1;
+        self.child.render_axes(cx);
+        cx.axes_bounds = axes_bounds;
+        cx.pixel_bounds = pixel_bounds;
+    }
+    fn value_at_x(&self, x: Self::X) -> Option<Self::Y> {
+        if !self.is_invertible() {
+            return None;
+        }
+        let y = self
+            .child
+            .value_at_x(inverse(x, self.scale.0, self.offset.0))?;
+        Some(forward(y, self.scale.1, self.offset.1))
+    }
+    fn visit_points_in(
+        &self,
+        x_range: &AxisRange<Self::X>,
+        f: &mut dyn FnMut(Point2<Self::X, Self::Y>),
+    ) {
+        if !self.is_invertible() {
+            return;
+        }
+        let (scale, offset) = (self.scale.0, self.offset.0);
+        let child_range = ascending(map_range(*x_range, |x| inverse(x, scale, offset)));
+        self.child
+            .visit_points_in(&child_range, &mut |point| f(self.forward(point)));
+    }
+    fn point_at(&self, index: usize) -> Option<Point2<Self::X, Self::Y>> {
+        self.child.point_at(index).map(|point| self.forward(point))
+    }
+    fn as_series_mut(&mut self) -> Option<&mut dyn Series> {
+        self.child.as_series_mut()
+    }
+    fn for_each_series(&mut self, f: &mut dyn FnMut(&mut dyn Series)) {
+        self.child.for_each_series(f)
+    }
+}
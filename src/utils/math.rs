@@ -1,5 +1,20 @@
+/// Significant digits kept by `display_double_smartly`
+pub const DEFAULT_SIGNIFICANT_DIGITS: usize = 6;
+
+/// Format `num` without trailing zeros, rounding away float noise like `0.30000000000000004`
 pub fn display_double_smartly(num: f64) -> String {
-    let mut formatted = num.to_string();
+    display_double_with_precision(num, DEFAULT_SIGNIFICANT_DIGITS)
+}
+/// Format `num` rounded to `significant` digits, then trim trailing zeros.
+/// Digits before the decimal point are never rounded away, so large values stay exact
+pub fn display_double_with_precision(num: f64, significant: usize) -> String {
+    let mut formatted = if num == 0.0 || !num.is_finite() {
+        num.to_string()
+    } else {
+        let magnitude = num.abs().log10().floor() as i64;
+        let decimals = (significant.max(1) as i64 - 1 - magnitude).max(0) as usize;
+        format!("{:.*}", decimals, num)
+    };
     if formatted.contains(".") {
         while formatted.ends_with("0") {
             formatted.pop();
@@ -8,5 +23,81 @@ pub fn display_double_smartly(num: f64) -> String {
     if formatted.ends_with(".") {
         formatted.pop();
     }
+    if formatted == "-0" {
+        formatted.remove(0);
+    }
     formatted
 }
+
+/// Round a step up to the closest 1, 2 or 5 times a power of ten
+pub fn nice_step(raw_step: f64) -> f64 {
+    if !raw_step.is_finite() || raw_step <= 0.0 {
+        return raw_step;
+    }
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let fraction = raw_step / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_away_float_noise() {
+        assert_eq!(display_double_smartly(0.1 + 0.2), "0.3");
+        assert_eq!(display_double_smartly(0.1 * 3.0), "0.3");
+        assert_eq!(display_double_smartly(1.1 * 1.1), "1.21");
+        assert_eq!(display_double_smartly(0.7 + 0.1), "0.8");
+        assert_eq!(display_double_smartly(1.0 - 0.9), "0.1");
+        assert_eq!(display_double_smartly(0.99999999), "1");
+    }
+
+    #[test]
+    fn trims_trailing_zeros_only_after_the_point() {
+        assert_eq!(display_double_smartly(100.0), "100");
+        assert_eq!(display_double_smartly(2.5), "2.5");
+        assert_eq!(display_double_smartly(-40.0), "-40");
+    }
+
+    #[test]
+    fn keeps_small_and_large_magnitudes() {
+        assert_eq!(display_double_smartly(1e-7), "0.0000001");
+        assert_eq!(display_double_smartly(-0.000123456789), "-0.000123457");
+        assert_eq!(display_double_smartly(123456789.0), "123456789");
+    }
+
+    #[test]
+    fn formats_zero_and_non_finite() {
+        assert_eq!(display_double_smartly(0.0), "0");
+        assert_eq!(display_double_smartly(-0.0), "0");
+        assert_eq!(display_double_smartly(f64::NAN), "NaN");
+        assert_eq!(display_double_smartly(f64::INFINITY), "inf");
+    }
+
+    #[test]
+    fn precision_is_configurable() {
+        assert_eq!(
+            display_double_with_precision(std::f64::consts::PI, 3),
+            "3.14"
+        );
+        assert_eq!(
+            display_double_with_precision(std::f64::consts::PI, 10),
+            "3.141592654"
+        );
+        assert_eq!(
+            display_double_with_precision(0.1 + 0.2, 17),
+            "0.30000000000000004"
+        );
+        assert_eq!(display_double_with_precision(0.26, 0), "0.3");
+    }
+}